@@ -0,0 +1,46 @@
+use std::time::{Duration, Instant};
+use conc::Interpreter;
+
+const FIB: &str = r#"
+{
+    clone u64_2 >=
+    { clone u64_1 - fib swap u64_2 - fib + } if
+} "fib" global_bind
+u64_22 fib
+"#;
+
+const REPEAT: &str = "u64_0 u64_300000 { u64_1 + } repeat";
+
+const WORD_CALLS: &str = r#"
+{ u64_1 + } "inc" global_bind
+u64_0 u64_300000 { inc } repeat
+"#;
+
+const RANGE: &str = r#"
+"stdlib/vector.cnc" use
+u64_100000 range
+"#;
+
+const FILTER: &str = r#"
+"stdlib/vector.cnc" use
+u64_100000 range { u64_3 % u64_0 == } filter
+"#;
+
+fn bench(name: &str, source: &str) {
+    let mut best = Duration::MAX;
+    for _ in 0..5 {
+        let mut interpreter = Interpreter::new();
+        let start = Instant::now();
+        interpreter.eval(source).unwrap();
+        best = best.min(start.elapsed());
+    }
+    println!("{:<12} {:>12.2?}", name, best);
+}
+
+fn main() {
+    bench("fib", FIB);
+    bench("repeat", REPEAT);
+    bench("word_calls", WORD_CALLS);
+    bench("range_100k", RANGE);
+    bench("filter_100k", FILTER);
+}
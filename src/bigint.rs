@@ -0,0 +1,383 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul, Neg, Sub}
+};
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>
+}
+
+fn trim(magnitude: &mut Vec<u32>) {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, limb) in long.iter().enumerate() {
+        let sum = *limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, limb) in a.iter().enumerate() {
+        let mut difference = *limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if difference < 0 {
+            difference += 1 << 32;
+            borrow = 1;
+        }
+        result.push(difference as u32);
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let product = *x as u64 * *y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        let mut k = i + b.len();
+        while carry > 0 {
+            let sum = result[k] as u64 + carry;
+            result[k] = sum as u32;
+            carry = sum >> 32;
+            k += 1;
+        }
+    }
+    trim(&mut result);
+    result
+}
+
+fn div_rem_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for i in (0..a.len()).rev() {
+        let current = (remainder << 32) | a[i] as u64;
+        quotient[i] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    trim(&mut quotient);
+    (quotient, remainder as u32)
+}
+
+fn shl_one(magnitude: &mut Vec<u32>) {
+    let mut carry = 0;
+    for limb in magnitude.iter_mut() {
+        let next = *limb >> 31;
+        *limb = (*limb << 1) | carry;
+        carry = next;
+    }
+    if carry > 0 {
+        magnitude.push(carry);
+    }
+}
+
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_magnitude(a, b) == Ordering::Less {
+        return (vec![], a.to_vec());
+    }
+    if b.len() == 1 {
+        let (quotient, remainder) = div_rem_small(a, b[0]);
+        let mut remainder = vec![remainder];
+        trim(&mut remainder);
+        return (quotient, remainder);
+    }
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = vec![];
+    for bit in (0..a.len() * 32).rev() {
+        shl_one(&mut remainder);
+        if (a[bit / 32] >> (bit % 32)) & 1 == 1 {
+            match remainder.first_mut() {
+                Some(limb) => *limb |= 1,
+                None => remainder.push(1)
+            }
+        }
+        if cmp_magnitude(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> BigInt {
+        trim(&mut magnitude);
+        BigInt { negative: negative && !magnitude.is_empty(), magnitude }
+    }
+
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.magnitude.first().is_some_and(|limb| limb & 1 == 1)
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt { negative: false, magnitude: self.magnitude.clone() }
+    }
+
+    pub fn div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.magnitude, &other.magnitude);
+        Some((
+            BigInt::from_parts(self.negative != other.negative, quotient),
+            BigInt::from_parts(self.negative, remainder)
+        ))
+    }
+
+    pub fn checked_div(&self, other: &BigInt) -> Option<BigInt> {
+        self.div_rem(other).map(|(quotient, _)| quotient)
+    }
+
+    pub fn checked_rem(&self, other: &BigInt) -> Option<BigInt> {
+        self.div_rem(other).map(|(_, remainder)| remainder)
+    }
+
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let remainder = a.checked_rem(&b).unwrap_or_default();
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> BigInt {
+        let mut result = BigInt::from(1u128);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    pub fn isqrt(&self) -> Option<BigInt> {
+        if self.negative {
+            return None;
+        }
+        if self.is_zero() {
+            return Some(BigInt::zero());
+        }
+        let bits = self.magnitude.len() * 32 - self.magnitude.last().map_or(32, |limb| limb.leading_zeros() as usize);
+        let mut root = BigInt::from(1u128).shl(bits / 2 + 1);
+        let two = BigInt::from(2u128);
+        loop {
+            let next = (&root + &self.checked_div(&root)?).checked_div(&two)?;
+            if next >= root {
+                return Some(root);
+            }
+            root = next;
+        }
+    }
+
+    pub fn shl(&self, bits: usize) -> BigInt {
+        let mut magnitude = vec![0u32; bits / 32];
+        let shift = bits % 32;
+        let mut carry = 0u32;
+        for limb in &self.magnitude {
+            if shift == 0 {
+                magnitude.push(*limb);
+            } else {
+                magnitude.push((*limb << shift) | carry);
+                carry = *limb >> (32 - shift);
+            }
+        }
+        magnitude.push(carry);
+        BigInt::from_parts(self.negative, magnitude)
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.negative || self.magnitude.len() > 4 {
+            return None;
+        }
+        Some(self.magnitude.iter().rev().fold(0u128, |acc, limb| (acc << 32) | *limb as u128))
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = BigInt { negative: false, magnitude: self.magnitude.clone() }.to_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self.magnitude.iter().rev().fold(0f64, |acc, limb| acc * 4294967296.0 + *limb as f64);
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    pub fn from_f64(value: f64) -> Option<BigInt> {
+        if !value.is_finite() {
+            return None;
+        }
+        let value = value.trunc();
+        if value.abs() < 1.0 {
+            return Some(BigInt::zero());
+        }
+        let bits = value.abs().to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i64 - 1075;
+        let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+        let magnitude = if exponent >= 0 {
+            BigInt::from(mantissa as u128).shl(exponent as usize)
+        } else {
+            BigInt::from((mantissa >> -exponent) as u128)
+        };
+        Some(if value < 0.0 { -&magnitude } else { magnitude })
+    }
+
+    pub fn parse(text: &str) -> Option<BigInt> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text))
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let mut magnitude: Vec<u32> = vec![];
+        for chunk in digits.as_bytes().chunks(9) {
+            let chunk = std::str::from_utf8(chunk).ok()?;
+            let scale = 10u32.pow(chunk.len() as u32);
+            magnitude = mul_magnitude(&magnitude, &[scale]);
+            magnitude = add_magnitude(&magnitude, &[chunk.parse().ok()?]);
+            trim(&mut magnitude);
+        }
+        Some(BigInt::from_parts(negative, magnitude))
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> BigInt {
+        let magnitude = (0..4).map(|i| (value >> (32 * i)) as u32).collect();
+        BigInt::from_parts(false, magnitude)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> BigInt {
+        let magnitude = BigInt::from(value.unsigned_abs()).magnitude;
+        BigInt::from_parts(value < 0, magnitude)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.magnitude, &other.magnitude));
+        }
+        match cmp_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::from_parts(other.negative, sub_magnitude(&other.magnitude, &self.magnitude)),
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.magnitude, &other.magnitude))
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != other.negative, mul_magnitude(&self.magnitude, &other.magnitude))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut chunks = vec![];
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = div_rem_small(&magnitude, 1_000_000_000);
+            chunks.push(remainder);
+            magnitude = quotient;
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap_or(0))?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,148 @@
+use std::{collections::HashMap, fmt, rc::Rc};
+
+use crate::{
+    error::Error,
+    lexer::Token,
+    math::Math,
+    text::Text,
+    map::MapWord,
+    vector::Vector,
+    parser::Node,
+    span::Span,
+    value::Value
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Builtin {
+    Add, Sub, Mul, Div, Rem,
+    WrappingAdd, WrappingSub, WrappingMul,
+    SaturatingAdd, SaturatingSub, SaturatingMul,
+    OverflowingAdd, OverflowingSub, OverflowingMul,
+    And, Or, Xor, Not, Shl, Shr, Rotl, Rotr,
+    PopCount, LeadingZeros, TrailingZeros,
+    True, False, Eq, Ne, Gt, Lt, Ge, Le,
+    StrictNumbers, RoundingMode, Round,
+    Print, Debug, Format,
+    Swap, Take, Clone, Delete,
+    GlobalBind, GlobalGet, ScopedBind, ScopedGet,
+    Cast, CheckedCast,
+    Insert, Remove, IntoVec,
+    If, Repeat, For, Call,
+    Use,
+    Math(Math),
+    Text(Text),
+    Map(MapWord),
+    Vector(Vector)
+}
+
+pub(crate) const BUILTINS: &[(&str, Builtin)] = &[
+    ("+", Builtin::Add), ("-", Builtin::Sub), ("*", Builtin::Mul), ("/", Builtin::Div), ("%", Builtin::Rem),
+    ("wrapping_+", Builtin::WrappingAdd), ("wrapping_-", Builtin::WrappingSub), ("wrapping_*", Builtin::WrappingMul),
+    ("saturating_+", Builtin::SaturatingAdd), ("saturating_-", Builtin::SaturatingSub), ("saturating_*", Builtin::SaturatingMul),
+    ("overflowing_+", Builtin::OverflowingAdd), ("overflowing_-", Builtin::OverflowingSub), ("overflowing_*", Builtin::OverflowingMul),
+    ("and", Builtin::And), ("or", Builtin::Or), ("xor", Builtin::Xor), ("not", Builtin::Not),
+    ("shl", Builtin::Shl), ("shr", Builtin::Shr), ("rotl", Builtin::Rotl), ("rotr", Builtin::Rotr),
+    ("popcount", Builtin::PopCount), ("leading_zeros", Builtin::LeadingZeros), ("trailing_zeros", Builtin::TrailingZeros),
+    ("true", Builtin::True), ("false", Builtin::False),
+    ("==", Builtin::Eq), ("!=", Builtin::Ne), (">", Builtin::Gt), ("<", Builtin::Lt), (">=", Builtin::Ge), ("<=", Builtin::Le),
+    ("strict_numbers", Builtin::StrictNumbers),
+    ("rounding_mode", Builtin::RoundingMode), ("round", Builtin::Round),
+    ("print", Builtin::Print), ("debug", Builtin::Debug), ("format", Builtin::Format),
+    ("swap", Builtin::Swap), ("take", Builtin::Take), ("clone", Builtin::Clone), ("delete", Builtin::Delete),
+    ("global_bind", Builtin::GlobalBind), ("global_get", Builtin::GlobalGet),
+    ("scoped_bind", Builtin::ScopedBind), ("scoped_get", Builtin::ScopedGet),
+    ("cast", Builtin::Cast), ("checked_cast", Builtin::CheckedCast),
+    ("insert", Builtin::Insert), ("remove", Builtin::Remove), ("into_vec", Builtin::IntoVec),
+    ("if", Builtin::If), ("repeat", Builtin::Repeat), ("for", Builtin::For), ("call", Builtin::Call),
+    ("use", Builtin::Use)
+];
+
+impl Builtin {
+    pub(crate) fn from_name(name: &str) -> Option<Builtin> {
+        BUILTINS.iter()
+            .find(|(builtin_name, _)| *builtin_name == name)
+            .map(|(_, builtin)| *builtin)
+            .or_else(|| Math::from_name(name).map(Builtin::Math))
+            .or_else(|| Text::from_name(name).map(Builtin::Text))
+            .or_else(|| MapWord::from_name(name).map(Builtin::Map))
+            .or_else(|| Vector::from_name(name).map(Builtin::Vector))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Op {
+    Push(Value),
+    Quote(Rc<Code>),
+    Map(Rc<Code>),
+    List(Rc<Code>),
+    Builtin(Builtin),
+    Word(usize)
+}
+
+#[derive(Clone, PartialEq, Default)]
+pub struct Code {
+    pub(crate) ops: Vec<Op>,
+    pub(crate) spans: Vec<Span>
+}
+
+impl fmt::Debug for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.ops).finish()
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Interner {
+    names: Vec<String>,
+    ids: HashMap<String, usize>
+}
+
+impl Interner {
+    pub(crate) fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub(crate) fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+}
+
+pub(crate) fn compile(tree: &[Node], words: &mut Interner) -> Result<Code, Error> {
+    let mut code = Code::default();
+    for node in tree {
+        let (op, span) = match node {
+            Node::Tok(Token::Number(number), span) => (Op::Push(Value::Number(number.clone())), span),
+            Node::Tok(Token::String(string), span) => (Op::Push(Value::String(string.clone())), span),
+            Node::Tok(Token::OpenBracket, span)
+            | Node::Tok(Token::OpenMap, span)
+            | Node::Tok(Token::OpenList, span)
+            | Node::Tok(Token::CloseBracket, span)
+            | Node::Tok(Token::CloseList, span) => {
+                return Err(Error::ParseError {
+                    message: "bracket appeared in ast".to_string(),
+                    span: span.clone()
+                })
+            },
+            Node::Group(inner, span) => (Op::Quote(Rc::new(compile(inner, words)?)), span),
+            Node::Map(inner, span) => (Op::Map(Rc::new(compile(inner, words)?)), span),
+            Node::List(inner, span) => (Op::List(Rc::new(compile(inner, words)?)), span),
+            Node::Tok(Token::Identifier(name), span) => match Builtin::from_name(name) {
+                Some(builtin) => (Op::Builtin(builtin), span),
+                None => (Op::Word(words.intern(name)), span)
+            }
+        };
+        code.ops.push(op);
+        code.spans.push(span.clone());
+    }
+    Ok(code)
+}
@@ -0,0 +1,289 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{bigint::BigInt, rational::Rational};
+
+pub const MAX_SCALE: u32 = 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    #[default]
+    HalfEven,
+    HalfUp,
+    HalfDown,
+    Up,
+    Down,
+    Ceiling,
+    Floor
+}
+
+impl RoundingMode {
+    pub fn from_name(name: &str) -> Option<RoundingMode> {
+        Some(match name {
+            "half_even" => RoundingMode::HalfEven,
+            "half_up" => RoundingMode::HalfUp,
+            "half_down" => RoundingMode::HalfDown,
+            "up" => RoundingMode::Up,
+            "down" => RoundingMode::Down,
+            "ceiling" => RoundingMode::Ceiling,
+            "floor" => RoundingMode::Floor,
+            _ => return None
+        })
+    }
+
+    /// Whether a quotient truncated toward zero should move one step away
+    /// from zero, given the sign of the exact result, how the discarded
+    /// remainder compares to one half, and whether the truncated quotient is
+    /// odd.
+    pub fn away_from_zero(self, negative: bool, exact: bool, half: Ordering, odd: bool) -> bool {
+        if exact {
+            return false;
+        }
+        match self {
+            RoundingMode::HalfEven => half == Ordering::Greater || (half == Ordering::Equal && odd),
+            RoundingMode::HalfUp => half != Ordering::Less,
+            RoundingMode::HalfDown => half == Ordering::Greater,
+            RoundingMode::Up => true,
+            RoundingMode::Down => false,
+            RoundingMode::Ceiling => !negative,
+            RoundingMode::Floor => negative,
+        }
+    }
+
+    pub fn round_float(self, value: f64) -> f64 {
+        let truncated = value.trunc();
+        let remainder = (value - truncated).abs();
+        let half = (remainder * 2.0).partial_cmp(&1.0).unwrap_or(Ordering::Less);
+        let odd = truncated % 2.0 != 0.0;
+        if self.away_from_zero(value < 0.0, remainder == 0.0, half, odd) {
+            truncated + value.signum()
+        } else {
+            truncated
+        }
+    }
+}
+
+fn round_div(numerator: i128, denominator: i128, mode: RoundingMode) -> Option<i128> {
+    let quotient = numerator.checked_div(denominator)?;
+    let remainder = numerator % denominator;
+    let negative = (numerator < 0) != (denominator < 0);
+    let half = (remainder.unsigned_abs() * 2).cmp(&denominator.unsigned_abs());
+    if mode.away_from_zero(negative, remainder == 0, half, quotient % 2 != 0) {
+        quotient.checked_add(if negative { -1 } else { 1 })
+    } else {
+        Some(quotient)
+    }
+}
+
+fn power_of_ten(exponent: u32) -> Option<i128> {
+    10i128.checked_pow(exponent)
+}
+
+#[derive(Clone, Copy)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u32) -> Option<Decimal> {
+        (scale <= MAX_SCALE).then_some(Decimal { mantissa, scale })
+    }
+
+    pub fn from_integer(value: i128) -> Decimal {
+        Decimal { mantissa: value, scale: 0 }
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        power_of_ten(self.scale).is_some_and(|unit| self.mantissa % unit == 0)
+    }
+
+    pub fn trunc(&self) -> i128 {
+        power_of_ten(self.scale).map_or(0, |unit| self.mantissa / unit)
+    }
+
+    pub fn rescale(&self, scale: u32, mode: RoundingMode) -> Option<Decimal> {
+        let mantissa = if scale >= self.scale {
+            self.mantissa.checked_mul(power_of_ten(scale - self.scale)?)?
+        } else {
+            round_div(self.mantissa, power_of_ten(self.scale - scale)?, mode)?
+        };
+        Decimal::new(mantissa, scale)
+    }
+
+    fn checked_align(&self, other: &Decimal) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        let first = self.rescale(scale, RoundingMode::Down)?;
+        let second = other.rescale(scale, RoundingMode::Down)?;
+        Some((first.mantissa, second.mantissa, scale))
+    }
+
+    fn align(&self, other: &Decimal) -> (i128, i128, u32) {
+        let scale = self.scale.max(other.scale);
+        let saturate = |value: &Decimal| value
+            .rescale(scale, RoundingMode::Down)
+            .map_or(if value.mantissa < 0 { i128::MIN } else { i128::MAX }, |value| value.mantissa);
+        (saturate(self), saturate(other), scale)
+    }
+
+    fn with_product_scale(mantissa: i128, scale: u32) -> Decimal {
+        let product = Decimal { mantissa, scale };
+        if scale > MAX_SCALE {
+            product.rescale(MAX_SCALE, RoundingMode::HalfEven).unwrap_or(product)
+        } else {
+            product
+        }
+    }
+
+    pub fn checked_add(&self, other: &Decimal) -> Option<Decimal> {
+        let (first, second, scale) = self.checked_align(other)?;
+        Some(Decimal { mantissa: first.checked_add(second)?, scale })
+    }
+
+    pub fn checked_sub(&self, other: &Decimal) -> Option<Decimal> {
+        let (first, second, scale) = self.checked_align(other)?;
+        Some(Decimal { mantissa: first.checked_sub(second)?, scale })
+    }
+
+    pub fn checked_mul(&self, other: &Decimal) -> Option<Decimal> {
+        let mantissa = self.mantissa.checked_mul(other.mantissa)?;
+        Some(Decimal::with_product_scale(mantissa, self.scale + other.scale))
+    }
+
+    /// Divides keeping the larger scale of the two operands, rounding the
+    /// last digit with `mode`.
+    pub fn checked_div(&self, other: &Decimal, mode: RoundingMode) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        let numerator = self.mantissa.checked_mul(power_of_ten(other.scale + scale - self.scale)?)?;
+        Some(Decimal { mantissa: round_div(numerator, other.mantissa, mode)?, scale })
+    }
+
+    pub fn checked_rem(&self, other: &Decimal) -> Option<Decimal> {
+        let (first, second, scale) = self.checked_align(other)?;
+        Some(Decimal { mantissa: first.checked_rem(second)?, scale })
+    }
+
+    pub fn combine(&self, other: &Decimal, op: fn(i128, i128) -> i128) -> Decimal {
+        let (first, second, scale) = self.align(other);
+        Decimal { mantissa: op(first, second), scale }
+    }
+
+    pub fn multiply(&self, other: &Decimal, op: fn(i128, i128) -> i128) -> Decimal {
+        Decimal::with_product_scale(op(self.mantissa, other.mantissa), self.scale + other.scale)
+    }
+
+    pub fn checked_abs(&self) -> Option<Decimal> {
+        Some(Decimal { mantissa: self.mantissa.checked_abs()?, scale: self.scale })
+    }
+
+    pub fn checked_pow(&self, mut exponent: u32) -> Option<Decimal> {
+        let mut result = Decimal::from_integer(1);
+        let mut base = *self;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+        Some(result)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    pub fn to_rational(&self) -> Rational {
+        let denominator = BigInt::from(10u128).pow(self.scale);
+        Rational::new(BigInt::from(self.mantissa), denominator)
+            .unwrap_or_else(|| Rational::from_integer(BigInt::zero()))
+    }
+
+    /// The nearest decimal of at most `MAX_SCALE` places, and whether it is
+    /// exactly equal to `value`.
+    pub fn from_rational(value: &Rational) -> Option<(Decimal, bool)> {
+        let (scaled, exact) = value.round_to(MAX_SCALE, RoundingMode::HalfEven);
+        let mantissa = scaled.to_i128()?;
+        let mut decimal = Decimal { mantissa, scale: MAX_SCALE };
+        while decimal.scale > 0 && decimal.mantissa % 10 == 0 {
+            decimal = Decimal { mantissa: decimal.mantissa / 10, scale: decimal.scale - 1 };
+        }
+        Some((decimal, exact))
+    }
+
+    /// Reads the positional form floats display as, so `0.1` becomes
+    /// `0.1` rather than the nearest binary fraction.
+    pub fn from_float_text(text: &str) -> Option<Decimal> {
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let mantissa = BigInt::parse(&format!("{}{}", integer, fraction))?;
+        let value = Rational::new(mantissa, BigInt::from(10u128).pow(fraction.len() as u32))?;
+        Decimal::from_rational(&value).map(|(decimal, _)| decimal)
+    }
+
+    pub fn parse(text: &str) -> Option<Decimal> {
+        let (integer, fraction) = match text.split_once('.') {
+            Some((_, "")) => return None,
+            Some(parts) => parts,
+            None => (text, "")
+        };
+        let digits = integer.strip_prefix(['-', '+']).unwrap_or(integer);
+        if digits.is_empty()
+            || !digits.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let mantissa = format!("{}{}", integer, fraction).parse().ok()?;
+        Decimal::new(mantissa, fraction.len() as u32)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        match self.checked_align(other) {
+            Some((first, second, _)) => first.cmp(&second),
+            None => self.to_rational().cmp(&other.to_rational())
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.scale == 0 {
+            return write!(f, "{}", self.mantissa);
+        }
+        let scale = self.scale as usize;
+        let digits = format!("{:0>width$}", self.mantissa.unsigned_abs(), width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
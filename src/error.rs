@@ -0,0 +1,138 @@
+use std::{fmt, io};
+
+use crate::span::Span;
+
+#[derive(Debug)]
+pub enum Error {
+    /// `unclosed` marks a string or comment that runs to the end of the
+    /// source, which more input could still complete.
+    LexError { message: String, span: Span, unclosed: bool },
+    ParseError { message: String, span: Span },
+    StackUnderflow,
+    TypeMismatch { expected: &'static str, found: &'static str },
+    UnknownWord(String),
+    WordAlreadyDefined(String),
+    StackEffectMismatch { word: String, expected: usize, found: usize },
+    UnknownVariable(String),
+    UnknownType(String),
+    UnknownRoundingMode(String),
+    InvalidCast { value: String, target: String },
+    IndexOutOfRange { index: usize, length: usize },
+    InvalidRange { start: usize, end: usize },
+    ArithmeticOverflow { operation: &'static str },
+    ShiftOutOfRange { amount: String, bits: u32 },
+    DivisionByZero,
+    OutOfDomain { operation: &'static str, value: String },
+    InvalidFormat(String),
+    MissingKey(String),
+    UnpairedMapKey(String),
+    Io { path: String, source: io::Error },
+    Located { error: Box<Error>, span: Span },
+    Multiple(Vec<Error>)
+}
+
+impl Error {
+    pub fn at(self, span: &Span) -> Error {
+        match self {
+            Error::LexError { .. }
+            | Error::ParseError { .. }
+            | Error::Located { .. }
+            | Error::Multiple(_) => self,
+            error => Error::Located { error: Box::new(error), span: span.clone() }
+        }
+    }
+
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::Located { error, .. } => error.root(),
+            error => error
+        }
+    }
+
+    /// The process exit code for this error. A located error takes the code
+    /// of the error it wraps, and a list of errors that of the first one.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Located { error, .. } => error.exit_code(),
+            Error::LexError { .. } => 3,
+            Error::ParseError { .. } => 4,
+            Error::StackUnderflow => 5,
+            Error::TypeMismatch { .. } => 6,
+            Error::UnknownWord(_) => 7,
+            Error::UnknownVariable(_) => 8,
+            Error::UnknownType(_) => 9,
+            Error::InvalidCast { .. } => 10,
+            Error::IndexOutOfRange { .. } => 11,
+            Error::ArithmeticOverflow { .. } => 12,
+            Error::DivisionByZero => 13,
+            Error::Io { .. } => 14,
+            Error::WordAlreadyDefined(_) => 15,
+            Error::StackEffectMismatch { .. } => 16,
+            Error::UnknownRoundingMode(_) => 17,
+            Error::OutOfDomain { .. } => 18,
+            Error::InvalidFormat(_) => 19,
+            Error::MissingKey(_) => 20,
+            Error::UnpairedMapKey(_) => 21,
+            Error::ShiftOutOfRange { .. } => 22,
+            Error::InvalidRange { .. } => 23,
+            Error::Multiple(errors) => errors.first().map_or(1, Error::exit_code)
+        }
+    }
+
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Error::LexError { span, .. }
+            | Error::ParseError { span, .. }
+            | Error::Located { span, .. } => Some(span),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LexError { message, .. } => write!(f, "Lex error: {}", message),
+            Error::ParseError { message, .. } => write!(f, "Parse error: {}", message),
+            Error::StackUnderflow => write!(f, "Stack empty when popped"),
+            Error::TypeMismatch { expected, found } => write!(f, "Wrong type: expected {}, found {}", expected, found),
+            Error::UnknownWord(word) => write!(f, "Unknown function: {}", word),
+            Error::WordAlreadyDefined(word) => write!(f, "Word already defined: {}", word),
+            Error::StackEffectMismatch { word, expected, found } => write!(f, "Native word '{}' returned {} values, declared {}", word, found, expected),
+            Error::UnknownVariable(name) => write!(f, "Variable not found: '{}'", name),
+            Error::UnknownType(name) => write!(f, "Unknown type: {}", name),
+            Error::UnknownRoundingMode(name) => write!(f, "Unknown rounding mode: {}", name),
+            Error::InvalidCast { value, target } => write!(f, "Error casting '{}' to '{}'", value, target),
+            Error::IndexOutOfRange { index, length } => write!(f, "Index {} out of range for length {}", index, length),
+            Error::InvalidRange { start, end } => write!(f, "Range start {} is past its end {}", start, end),
+            Error::ArithmeticOverflow { operation } => write!(f, "Arithmetic overflow in '{}'", operation),
+            Error::ShiftOutOfRange { amount, bits } => write!(f, "Shift amount {} out of range for a {}-bit integer", amount, bits),
+            Error::DivisionByZero => write!(f, "Division by zero"),
+            Error::OutOfDomain { operation, value } => write!(f, "'{}' is not defined for {}", operation, value),
+            Error::InvalidFormat(message) => write!(f, "Invalid format string: {}", message),
+            Error::MissingKey(key) => write!(f, "Key not found in map: {}", key),
+            Error::UnpairedMapKey(key) => write!(f, "Map literal key '{}' has no value", key),
+            Error::Io { path, source } => write!(f, "Couldn't read '{}': {}", path, source),
+            Error::Located { error, .. } => write!(f, "{}", error),
+            Error::Multiple(errors) => {
+                for (index, error) in errors.iter().enumerate() {
+                    if index > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Located { error, .. } => error.source(),
+            _ => None
+        }
+    }
+}
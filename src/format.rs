@@ -0,0 +1,214 @@
+use crate::{
+    bigint::BigInt,
+    decimal::RoundingMode,
+    error::Error,
+    number::Number,
+    runtime::State,
+    value::Value
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+    Center
+}
+
+#[derive(Debug, Default)]
+struct Spec {
+    fill: Option<char>,
+    align: Option<Align>,
+    sign: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>
+}
+
+enum Segment {
+    Literal(String),
+    Value(Spec)
+}
+
+fn invalid(template: &str, reason: &str) -> Error {
+    Error::InvalidFormat(format!("{} in '{}'", reason, template))
+}
+
+fn align_from(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Center),
+        _ => None
+    }
+}
+
+/// Reads a width or precision, which as in Rust's own format strings must
+/// fit in a `u16`.
+fn digits(chars: &mut std::iter::Peekable<std::str::Chars>, template: &str) -> Result<Option<usize>, Error> {
+    let mut text = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        text.push(c);
+    }
+    if text.is_empty() {
+        return Ok(None);
+    }
+    text.parse::<u16>()
+        .map(|value| Some(value as usize))
+        .map_err(|_| invalid(template, &format!("width or precision {} too large", text)))
+}
+
+/// Reads `[[fill]align][+][0][width][.precision]`, the subset of Rust's
+/// format spec that makes sense for stack values.
+fn parse_spec(text: &str, template: &str) -> Result<Spec, Error> {
+    let mut spec = Spec::default();
+    let mut lookahead = text.chars();
+    match (lookahead.next(), lookahead.next().and_then(align_from)) {
+        (Some(fill), Some(align)) => {
+            spec.fill = Some(fill);
+            spec.align = Some(align);
+        },
+        (Some(c), _) => spec.align = align_from(c),
+        _ => ()
+    }
+    let skip = match (spec.fill, spec.align) {
+        (Some(_), _) => 2,
+        (None, Some(_)) => 1,
+        _ => 0
+    };
+    let mut chars = text.chars().skip(skip).collect::<String>();
+    if let Some(rest) = chars.strip_prefix('+') {
+        spec.sign = true;
+        chars = rest.to_string();
+    }
+    if let Some(rest) = chars.strip_prefix('0') {
+        spec.zero = true;
+        chars = rest.to_string();
+    }
+    let mut chars = chars.chars().peekable();
+    spec.width = digits(&mut chars, template)?.unwrap_or(0);
+    if chars.next_if_eq(&'.').is_some() {
+        spec.precision = Some(digits(&mut chars, template)?.ok_or_else(|| invalid(template, "missing precision"))?);
+    }
+    match chars.next() {
+        Some(c) => Err(invalid(template, &format!("unexpected '{}' in format spec", c))),
+        None => Ok(spec)
+    }
+}
+
+fn parse(template: &str) -> Result<Vec<Segment>, Error> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                literal.push('{');
+            },
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                literal.push('}');
+            },
+            '{' => {
+                let (inside, rest) = chars.as_str().split_once('}')
+                    .ok_or_else(|| invalid(template, "unclosed '{'"))?;
+                let spec = match inside {
+                    "" => Spec::default(),
+                    inside => match inside.strip_prefix(':') {
+                        Some(spec) => parse_spec(spec, template)?,
+                        None => return Err(invalid(template, "placeholders take no argument name"))
+                    }
+                };
+                chars = rest.chars();
+                segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                segments.push(Segment::Value(spec));
+            },
+            '}' => return Err(invalid(template, "unmatched '}'")),
+            c => literal.push(c)
+        }
+    }
+    segments.push(Segment::Literal(literal));
+    Ok(segments)
+}
+
+/// Writes `value` (already scaled by `10^places`) with a decimal point
+/// `places` digits from the right.
+fn scaled(value: &BigInt, places: usize) -> String {
+    let digits = format!("{:0>width$}", value.abs().to_string(), width = places + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - places);
+    let sign = if value.is_negative() { "-" } else { "" };
+    match fraction {
+        "" => format!("{}{}", sign, integer),
+        fraction => format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
+fn number_text(value: &Number, precision: Option<usize>, mode: RoundingMode) -> Result<String, Error> {
+    let Some(places) = precision else {
+        return Ok(value.to_string());
+    };
+    Ok(match value {
+        Number::F32(value) => format!("{:.*}", places, value),
+        Number::F64(value) => format!("{:.*}", places, value),
+        Number::Decimal(value) => value.rescale(places as u32, mode)
+            .ok_or(Error::ArithmeticOverflow { operation: "format" })?
+            .to_string(),
+        Number::Rational(value) => scaled(&value.round_to(places as u32, mode).0, places),
+        value => value.to_string()
+    })
+}
+
+fn render(value: &Value, spec: &Spec, mode: RoundingMode) -> Result<String, Error> {
+    let (mut body, numeric) = match value {
+        Value::Number(number) => (number_text(number, spec.precision, mode)?, true),
+        Value::String(text) => match spec.precision {
+            Some(places) => (text.chars().take(places).collect(), false),
+            None => (text.clone(), false)
+        },
+        value => (value.to_string(), false)
+    };
+    if numeric && spec.sign && !body.starts_with('-') {
+        body.insert(0, '+');
+    }
+    let length = body.chars().count();
+    if length >= spec.width {
+        return Ok(body);
+    }
+    let padding = spec.width - length;
+    if numeric && spec.zero && spec.align.is_none() {
+        let sign = if body.starts_with(['+', '-']) { 1 } else { 0 };
+        body.insert_str(sign, &"0".repeat(padding));
+        return Ok(body);
+    }
+    let fill = spec.fill.unwrap_or(' ').to_string();
+    let default = if numeric { Align::Right } else { Align::Left };
+    let (before, after) = match spec.align.unwrap_or(default) {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Center => (padding / 2, padding - padding / 2)
+    };
+    Ok(format!("{}{}{}", fill.repeat(before), body, fill.repeat(after)))
+}
+
+/// Pops a template and one value per placeholder, filling the placeholders
+/// left to right from the deepest of those values.
+pub(crate) fn format(state: &mut State) -> Result<(), Error> {
+    let template = state.pop_string()?;
+    let segments = parse(&template)?;
+    let count = segments.iter().filter(|segment| matches!(segment, Segment::Value(_))).count();
+    let start = state.stack.len().checked_sub(count).ok_or(Error::StackUnderflow)?;
+    let values = state.stack.split_off(start);
+    let mut values = values.iter();
+    let mut result = String::new();
+    for segment in &segments {
+        match segment {
+            Segment::Literal(text) => result.push_str(text),
+            Segment::Value(spec) => {
+                let value = values.next().ok_or(Error::StackUnderflow)?;
+                result.push_str(&render(value, spec, state.rounding)?);
+            }
+        }
+    }
+    state.stack.push(Value::String(result));
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::rc::Rc;
+
+use crate::{
+    error::Error,
+    lexer::lex,
+    parser::parse,
+    decimal::RoundingMode,
+    runtime::{check_bindable, slot, State, StackEffect, NativeWord},
+    value::Value
+};
+
+/// A conc interpreter whose stack and globals persist between evaluations.
+#[derive(Default)]
+pub struct Interpreter {
+    state: State
+}
+
+impl Clone for Interpreter {
+    fn clone(&self) -> Interpreter {
+        Interpreter { state: self.state.fork() }
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter::default()
+    }
+
+    pub fn eval(&mut self, source: &str) -> Result<(), Error> {
+        self.eval_named(source, "<eval>")
+    }
+
+    /// Evaluates `source`, using `name` as the file name in error locations.
+    pub fn eval_named(&mut self, source: &str, name: &str) -> Result<(), Error> {
+        let code = self.state.compile(&parse(lex(source.to_string(), name)?)?)?;
+        self.state.run(&code)
+    }
+
+    pub fn push(&mut self, value: Value) {
+        self.state.stack.push(value);
+    }
+
+    pub fn pop(&mut self) -> Result<Value, Error> {
+        self.state.pop()
+    }
+
+    pub fn peek(&self) -> Option<&Value> {
+        self.state.stack.last()
+    }
+
+    pub fn stack(&self) -> &[Value] {
+        &self.state.stack
+    }
+
+    /// When enabled, arithmetic and comparison require both operands to be
+    /// the same number type instead of promoting them.
+    pub fn set_strict_numbers(&mut self, strict: bool) {
+        self.state.strict_numbers = strict;
+    }
+
+    /// How `/` rounds decimal results and how `round` breaks ties.
+    pub fn set_rounding_mode(&mut self, mode: RoundingMode) {
+        self.state.rounding = mode;
+    }
+
+    pub fn global(&self, name: &str) -> Option<Value> {
+        self.state.global_get(self.state.words.get(name)?)
+    }
+
+    /// Sets a global as `global_bind` would, so builtin names are rejected.
+    pub fn set_global(&mut self, name: &str, value: Value) -> Result<(), Error> {
+        check_bindable(name)?;
+        let id = self.state.words.intern(name);
+        *slot(&mut self.state.global, id) = Some(value);
+        Ok(())
+    }
+
+    /// Registers a Rust closure as the word `name`. The closure receives
+    /// `effect.inputs` values in push order and must return exactly
+    /// `effect.outputs` values, which are pushed in order. Builtins and
+    /// words that are already registered can't be replaced.
+    pub fn register<F>(&mut self, name: &str, effect: StackEffect, function: F) -> Result<(), Error>
+    where
+        F: Fn(Vec<Value>) -> Result<Vec<Value>, Error> + 'static
+    {
+        check_bindable(name)?;
+        let id = self.state.words.intern(name);
+        let native = slot(&mut self.state.native, id);
+        if native.is_some() {
+            return Err(Error::WordAlreadyDefined(name.to_string()));
+        }
+        *native = Some(NativeWord { effect, function: Rc::new(function) });
+        Ok(())
+    }
+}
@@ -1,88 +1,385 @@
-use crate::number::Number;
+use std::{rc::Rc, str::Chars};
+
+use crate::{
+    error::Error,
+    bigint::BigInt,
+    decimal::Decimal,
+    number::{CastMode, Number},
+    rational::Rational,
+    span::{Source, Span}
+};
 
 const WHITESPACE: &str = " \t\n";
+pub const UNCLOSED_STRING: &str = "String not closed";
+pub const UNCLOSED_COMMENT: &str = "Comment not closed";
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     OpenBracket,
+    OpenMap,
     CloseBracket,
+    OpenList,
+    CloseList,
     Identifier(String),
     String(String),
     Number(Number)
 }
 
-macro_rules! parse_num {
-    ($builder:expr, $result:expr, $result_type:ty, $variant:ident) => {
-        let text = &$builder[stringify!($result_type).len()+1..];
-        let value: $result_type = text.parse().map_err(|_| format!("Couldnt parse {}: {}", stringify!($result_type), text))?;
-        $result.push(Token::Number(Number::$variant(value)));
-        $builder = String::new();
+struct Cursor<'a> {
+    chars: Chars<'a>,
+    line: usize,
+    column: usize
+}
+
+impl Cursor<'_> {
+    fn next(&mut self) -> Option<char> {
+        let next = self.chars.next()?;
+        if next == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(next)
+    }
+
+    fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    fn rest(&self) -> &str {
+        self.chars.as_str()
+    }
+}
+
+type Positioned = Vec<(char, (usize, usize))>;
+
+/// Reads string contents up to `terminator`, which is consumed. With
+/// `escapes`, a backslash keeps the following character from ending the
+/// string; decoding happens later in `unescape`.
+fn read_string(chars: &mut Cursor, terminator: &str, escapes: bool) -> Option<Positioned> {
+    let mut content = vec![];
+    loop {
+        if chars.rest().starts_with(terminator) {
+            for _ in terminator.chars() {
+                chars.next();
+            }
+            return Some(content);
+        }
+        let position = chars.position();
+        let next = chars.next()?;
+        content.push((next, position));
+        if escapes && next == '\\' {
+            let position = chars.position();
+            content.push((chars.next()?, position));
+        }
+    }
+}
+
+/// Drops a line break right after the opening quotes and a blank last line,
+/// then removes the indentation shared by every non-blank line.
+fn dedent(content: Positioned) -> Positioned {
+    let mut lines: Vec<&[(char, (usize, usize))]> = content.split(|(c, _)| *c == '\n').collect();
+    let blank = |line: &[(char, (usize, usize))]| line.iter().all(|(c, _)| *c == ' ' || *c == '\t');
+    if lines.len() > 1 && lines[0].is_empty() {
+        lines.remove(0);
+    }
+    if lines.len() > 1 && lines.last().is_some_and(|line| blank(line)) {
+        lines.pop();
+    }
+    let indent = lines.iter()
+        .filter(|line| !blank(line))
+        .map(|line| line.iter().take_while(|(c, _)| *c == ' ' || *c == '\t').count())
+        .min()
+        .unwrap_or(0);
+    let mut result = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            result.push(('\n', (0, 0)));
+        }
+        result.extend_from_slice(line.get(indent..).unwrap_or(&[]));
+    }
+    result
+}
+
+fn unescape(content: &[(char, (usize, usize))], span: &dyn Fn((usize, usize), usize) -> Span) -> Result<String, Error> {
+    let mut result = String::new();
+    let mut chars = content.iter();
+    while let Some(&(next, position)) = chars.next() {
+        if next != '\\' {
+            result.push(next);
+            continue;
+        }
+        let invalid = |message: String, length: usize| Error::LexError { message, span: span(position, length), unclosed: false };
+        let escaped = chars.next().map_or('\\', |(escaped, _)| *escaped);
+        result.push(match escaped {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            'u' => {
+                let mut sequence = String::from("\\u");
+                let mut closed = false;
+                for (c, _) in chars.by_ref().take(9) {
+                    sequence.push(*c);
+                    if *c == '}' {
+                        closed = true;
+                        break;
+                    }
+                }
+                let digits = sequence.strip_prefix("\\u{").and_then(|rest| rest.strip_suffix('}'));
+                let length = sequence.chars().count();
+                digits
+                    .filter(|digits| closed && (1..=6).contains(&digits.len()))
+                    .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid(format!("invalid unicode escape '{}'", sequence), length))?
+            },
+            other => return Err(invalid(format!("invalid escape '\\{}'", other), 2))
+        });
+    }
+    Ok(result)
+}
+
+const INTEGER_TYPES: &[&str] = &["i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128"];
+const FLOAT_TYPES: &[&str] = &["f32", "f64"];
+
+fn invalid_literal(text: &str) -> String {
+    format!("invalid number literal '{}'", text)
+}
+
+fn out_of_range(text: &str, result_type: &str) -> String {
+    format!("literal '{}' out of range for {}", text, result_type)
+}
+
+/// Parses an optionally negative integer in decimal, `0x` hex, `0o` octal or
+/// `0b` binary, with `_` separators, and checks it fits `result_type`.
+fn integer_literal(text: &str, body: &str, result_type: &str) -> Result<Number, String> {
+    let (negative, body) = match body.strip_prefix('-') {
+        Some(body) => (true, body),
+        None => (false, body)
+    };
+    let (radix, digits) = match body.get(..2) {
+        Some("0x") => (16, &body[2..]),
+        Some("0o") => (8, &body[2..]),
+        Some("0b") => (2, &body[2..]),
+        _ => (10, body)
+    };
+    let digits: String = digits.chars().filter(|c| *c != '_').collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(invalid_literal(text));
+    }
+    let magnitude = u128::from_str_radix(&digits, radix).map_err(|_| out_of_range(text, result_type))?;
+    let value = if negative {
+        0i128.checked_sub_unsigned(magnitude).map(Number::I128)
+    } else {
+        Some(Number::U128(magnitude))
     };
+    value
+        .and_then(|value| value.cast_to(result_type, CastMode::Checked).ok())
+        .ok_or_else(|| out_of_range(text, result_type))
 }
 
-pub fn lex(text: String) -> Result<Vec<Token>, String> {
+fn float_literal(text: &str, body: &str, result_type: &str) -> Result<Number, String> {
+    let body: String = body.chars().filter(|c| *c != '_').collect();
+    let infinite = body.to_ascii_lowercase().contains("inf");
+    let value = match result_type {
+        "f32" => body.parse::<f32>().map(Number::F32),
+        _ => body.parse::<f64>().map(Number::F64)
+    }.map_err(|_| invalid_literal(text))?;
+    match value {
+        Number::F32(value) if value.is_infinite() && !infinite => Err(out_of_range(text, result_type)),
+        Number::F64(value) if value.is_infinite() && !infinite => Err(out_of_range(text, result_type)),
+        value => Ok(value)
+    }
+}
+
+/// Splits a bare literal into its digits and type suffix. Words such as
+/// `2dup` or `1+`, a single digit followed by something that doesn't
+/// continue a number, are not literals and give `None`. Anything else that
+/// starts like a number but isn't one, such as `0xZZ` or `12abc`, is an
+/// invalid literal.
+fn literal_shape(text: &str) -> Option<Result<(&str, Option<&str>), String>> {
+    let unsigned = text.strip_prefix('-').unwrap_or(text);
+    if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let sign = text.len() - unsigned.len();
+    let radix = matches!(unsigned.get(..2), Some("0x" | "0o" | "0b"));
+    let digits = |from: usize| unsigned[from..]
+        .find(|c: char| !(c.is_ascii_hexdigit() && (radix || c.is_ascii_digit()) || c == '_'))
+        .map_or(unsigned.len(), |end| from + end);
+    let mut end = if radix { digits(2) } else { digits(0) };
+    if radix && end == 2 {
+        return Some(Err(invalid_literal(text)));
+    }
+    if !radix {
+        if unsigned[end..].starts_with('.') {
+            let fraction = digits(end + 1);
+            if fraction > end + 1 {
+                end = fraction;
+            }
+        }
+        if unsigned[end..].starts_with(['e', 'E']) {
+            let exponent = end + 1 + unsigned[end + 1..].starts_with(['+', '-']) as usize;
+            let exponent_end = digits(exponent);
+            if exponent_end > exponent {
+                end = exponent_end;
+            }
+        }
+    }
+    let (body, suffix) = text.split_at(sign + end);
+    match suffix {
+        "" => Some(Ok((body, None))),
+        suffix if INTEGER_TYPES.contains(&suffix) => Some(Ok((body, Some(suffix)))),
+        suffix if !radix && FLOAT_TYPES.contains(&suffix) => Some(Ok((body, Some(suffix)))),
+        _ if !radix && end == 1 => None,
+        _ => Some(Err(invalid_literal(text)))
+    }
+}
+
+/// A literal without a type prefix: `255`, `-3`, `0xFF`, `1_000`, `2.5e-3`,
+/// optionally followed by a type suffix as in `255u8` or `1.5f32`. Bare
+/// integers are `u64`, or `i64` when negative, and the rest are `f64`.
+fn bare_literal(text: &str, body: &str, suffix: Option<&str>) -> Result<Number, String> {
+    let radix = matches!(body.strip_prefix('-').unwrap_or(body).get(..2), Some("0x" | "0o" | "0b"));
+    let float = !radix && (body.contains(['.', 'e', 'E']) || suffix.is_some_and(|suffix| FLOAT_TYPES.contains(&suffix)));
+    match suffix {
+        Some(suffix) if float && INTEGER_TYPES.contains(&suffix) => {
+            Err(format!("integer suffix '{}' on float literal '{}'", suffix, text))
+        },
+        suffix if float => float_literal(text, body, suffix.unwrap_or("f64")),
+        Some(suffix) => integer_literal(text, body, suffix),
+        None if text.starts_with('-') => integer_literal(text, body, "i64"),
+        None => integer_literal(text, body, "u64")
+    }
+}
+
+pub(crate) fn number_literal(text: &str) -> Option<Result<Number, String>> {
+    if let Some((prefix, body)) = text.split_once('_') {
+        if INTEGER_TYPES.contains(&prefix) {
+            return Some(integer_literal(text, body, prefix));
+        }
+        if FLOAT_TYPES.contains(&prefix) {
+            return Some(float_literal(text, body, prefix));
+        }
+        // A `dec_` literal's scale is the number of digits written after the
+        // point, and division keeps the larger scale of its operands, so
+        // `dec_1 dec_2 /` is `0` while `dec_1.00 dec_2 /` is `0.50`.
+        if matches!(prefix, "big" | "ratio" | "dec") {
+            let digits: String = body.chars().filter(|c| *c != '_').collect();
+            let exact = match prefix {
+                "big" => BigInt::parse(&digits).map(Number::BigInt),
+                "ratio" => Rational::parse(&digits).map(Number::Rational),
+                _ => Decimal::parse(&digits).map(Number::Decimal)
+            };
+            return Some(exact.ok_or_else(|| invalid_literal(text)));
+        }
+    }
+    Some(literal_shape(text)?.and_then(|(body, suffix)| bare_literal(text, body, suffix)))
+}
+
+fn bracket(next: char) -> Option<Token> {
+    match next {
+        '{' => Some(Token::OpenBracket),
+        '}' => Some(Token::CloseBracket),
+        '[' => Some(Token::OpenList),
+        ']' => Some(Token::CloseList),
+        _ => None
+    }
+}
+
+fn word(text: String, span: &Span) -> Result<Token, Error> {
+    match number_literal(&text) {
+        Some(number) => number
+            .map(Token::Number)
+            .map_err(|message| Error::LexError { message, span: span.clone(), unclosed: false }),
+        None => Ok(Token::Identifier(text))
+    }
+}
+
+/// Splits `text` into tokens. Words end at whitespace, and brackets,
+/// quotes and comments end the word before them, so `{u64_1}` is three
+/// tokens. Comments are either `; ... ;` or `//` to the end of the line.
+pub fn lex(text: String, file_name: &str) -> Result<Vec<(Token, Span)>, Error> {
 
     let mut result = vec![];
     let mut builder = String::new();
-    let lex_text = text + "\n";
-    let mut chars = lex_text.chars();
-
-    while let Some(next) = chars.next() {
-        if WHITESPACE.contains(next) && builder.trim() != "" {
-            builder = builder.trim().to_string();
-            if builder.starts_with("i8_") {
-                parse_num!(builder, result, i8, I8);
-            } else if builder.starts_with("i16_") {
-                parse_num!(builder, result, i16, I16);
-            } else if builder.starts_with("i32_") {
-                parse_num!(builder, result, i32, I32);
-            } else if builder.starts_with("i64_") {
-                parse_num!(builder, result, i64, I64);
-            } else if builder.starts_with("i128_") {
-                parse_num!(builder, result, i128, I128);
-            } else if builder.starts_with("u8_") {
-                parse_num!(builder, result, u8, U8);
-            } else if builder.starts_with("u16_") {
-                parse_num!(builder, result, u16, U16);
-            } else if builder.starts_with("u32_") {
-                parse_num!(builder, result, u32, U32);
-            } else if builder.starts_with("u64_") {
-                parse_num!(builder, result, u64, U64);
-            } else if builder.starts_with("u128_") {
-                parse_num!(builder, result, u128, U128);
-            } else if builder.starts_with("f32_") {
-                parse_num!(builder, result, f32, F32);
-            } else if builder.starts_with("f64_") {
-                parse_num!(builder, result, f64, F64);
-            } else if let Ok(val) = builder.parse::<u64>() {
-                result.push(Token::Number(Number::U64(val)));
-                builder = String::new();
-            } else if let Ok(val) = builder.parse::<f64>() {
-                result.push(Token::Number(Number::F64(val)));
-                builder = String::new();
-            } else if builder == "{" {
-                result.push(Token::OpenBracket);
-                builder = String::new();
-            } else if builder == "}" {
-                result.push(Token::CloseBracket);
-                builder = String::new();
-            } else {
-                result.push(Token::Identifier(builder));
-                builder = String::new();
-            }
+    let lex_text = text.clone() + "\n";
+    let source = Rc::new(Source { name: file_name.to_string(), text });
+    let mut chars = Cursor { chars: lex_text.chars(), line: 1, column: 1 };
+    let mut start = chars.position();
+    let span = |(line, column): (usize, usize), length: usize| Span {
+        source: source.clone(),
+        line,
+        column,
+        length
+    };
+
+    loop {
+        let position = chars.position();
+        let Some(next) = chars.next() else { break };
+        let hashes = builder.strip_prefix('r')
+            .filter(|hashes| next == '"' && hashes.chars().all(|c| c == '#'))
+            .map(str::len);
+        let opens_map = next == '#' && chars.rest().starts_with('{');
+        let delimits = WHITESPACE.contains(next)
+            || next == ';'
+            || bracket(next).is_some()
+            || opens_map
+            || (next == '"' && hashes.is_none());
+        if delimits && !builder.is_empty() {
+            let span = span(start, builder.chars().count());
+            result.push((word(std::mem::take(&mut builder), &span)?, span));
+        }
+
+        if WHITESPACE.contains(next) {
+            continue;
+        } else if let Some(token) = bracket(next) {
+            result.push((token, span(position, 1)));
+        } else if opens_map {
+            chars.next();
+            result.push((Token::OpenMap, span(position, 2)));
         } else if next == '"' {
-            let mut string_char = chars.next()
-                .ok_or_else(|| "String not closed".to_string())?;
-            while string_char != '"' {
-                builder.push(string_char);
-                string_char = chars.next()
-                    .ok_or_else(|| "String not closed".to_string())?;
-            }
-            result.push(Token::String(builder));
-            builder = String::new();
+            let open = if hashes.is_some() { start } else { position };
+            let not_closed = || Error::LexError {
+                message: UNCLOSED_STRING.to_string(),
+                span: span(open, 1),
+                unclosed: true
+            };
+            let string = if let Some(hashes) = hashes {
+                builder.clear();
+                let content = read_string(&mut chars, &format!("\"{}", "#".repeat(hashes)), false)
+                    .ok_or_else(not_closed)?;
+                content.into_iter().map(|(c, _)| c).collect()
+            } else if chars.rest().starts_with("\"\"") {
+                chars.next();
+                chars.next();
+                let content = read_string(&mut chars, "\"\"\"", true).ok_or_else(not_closed)?;
+                unescape(&dedent(content), &span)?
+            } else {
+                let content = read_string(&mut chars, "\"", true).ok_or_else(not_closed)?;
+                unescape(&content, &span)?
+            };
+            let (line, column) = chars.position();
+            let length = if line == open.0 { column - open.1 } else { 1 };
+            result.push((Token::String(string), span(open, length)));
         } else if next == ';' {
-            while chars.next().unwrap() != ';' {}
+            if !std::iter::from_fn(|| chars.next()).any(|c| c == ';') {
+                return Err(Error::LexError {
+                    message: UNCLOSED_COMMENT.to_string(),
+                    span: span(position, 1),
+                    unclosed: true
+                });
+            }
+        } else if next == '/' && builder.is_empty() && chars.rest().starts_with('/') {
+            std::iter::from_fn(|| chars.next()).find(|c| *c == '\n');
         } else {
+            if builder.is_empty() {
+                start = position;
+            }
             builder.push(next);
         }
     }
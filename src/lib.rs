@@ -3,3 +3,20 @@ pub mod parser;
 pub mod runtime;
 pub mod value;
 pub mod number;
+pub mod bigint;
+pub mod rational;
+pub mod decimal;
+mod math;
+mod text;
+mod format;
+pub mod map;
+mod vector;
+pub mod span;
+pub mod bytecode;
+mod scope;
+pub mod error;
+pub mod interpreter;
+
+pub use error::Error;
+pub use interpreter::Interpreter;
+pub use runtime::StackEffect;
@@ -1,14 +1,35 @@
-use std::{fs, env};
-use conc::{lexer, parser, runtime};
-
-fn main() -> Result<(), String> {
-    let file_name = env::args()
-        .nth(1)
-        .ok_or_else(|| "Not enough arguments provided".to_string())?;
-    let text = fs::read_to_string(&file_name)
-        .map_err(|_| format!("Source file not found: '{}'", file_name))?;
-    let tokens = lexer::lex(text)?;
+mod repl;
+
+use std::{fs, env, process};
+use conc::{lexer, parser, runtime, Error};
+
+pub fn report(error: &Error) {
+    if let Error::Multiple(errors) = error {
+        errors.iter().for_each(report);
+        return;
+    }
+    eprintln!("error: {}", error);
+    if let Some(span) = error.span() {
+        eprintln!(" --> {}\n{}", span, span.excerpt());
+    }
+}
+
+fn run(file_name: &str) -> Result<(), Error> {
+    let text = fs::read_to_string(file_name)
+        .map_err(|source| Error::Io { path: file_name.to_string(), source })?;
+    let tokens = lexer::lex(text, file_name)?;
     let tree = parser::parse(tokens)?;
     runtime::run_tree(tree)?;
     Ok(())
 }
+
+fn main() {
+    let Some(file_name) = env::args().nth(1) else {
+        repl::run();
+        return;
+    };
+    if let Err(error) = run(&file_name) {
+        report(&error);
+        process::exit(error.exit_code());
+    }
+}
@@ -0,0 +1,170 @@
+use std::{cmp::Ordering, collections::BTreeMap, fmt, rc::Rc};
+
+use crate::{
+    error::Error,
+    number::Number,
+    runtime::State,
+    value::Value
+};
+
+/// A map key. Numbers of different types are different keys, so `u64_1`
+/// and `i32_1` can both be present in the same map.
+#[derive(Debug, Clone)]
+pub enum Key {
+    Boolean(bool),
+    Number(Number),
+    String(String)
+}
+
+pub type Map = BTreeMap<Key, Value>;
+
+impl Key {
+    fn rank(&self) -> u8 {
+        match self {
+            Key::Boolean(_) => 0,
+            Key::Number(_) => 1,
+            Key::String(_) => 2
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        match self {
+            Key::Boolean(value) => Value::Boolean(value),
+            Key::Number(value) => Value::Number(value),
+            Key::String(value) => Value::String(value)
+        }
+    }
+}
+
+impl TryFrom<Value> for Key {
+    type Error = Error;
+    fn try_from(value: Value) -> Result<Key, Error> {
+        match value {
+            Value::Boolean(value) => Ok(Key::Boolean(value)),
+            Value::Number(value) => Ok(Key::Number(value)),
+            Value::String(value) => Ok(Key::String(value)),
+            value => Err(Error::TypeMismatch { expected: "map key", found: value.type_name() })
+        }
+    }
+}
+
+fn compare_numbers(first: &Number, second: &Number) -> Ordering {
+    match (first, second) {
+        (Number::F32(first), Number::F32(second)) => first.total_cmp(second),
+        (Number::F64(first), Number::F64(second)) => first.total_cmp(second),
+        _ if first.type_name() != second.type_name() => first.type_name().cmp(second.type_name()),
+        _ => first.partial_cmp(second).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Key) -> Ordering {
+        match (self, other) {
+            (Key::Boolean(first), Key::Boolean(second)) => first.cmp(second),
+            (Key::Number(first), Key::Number(second)) => compare_numbers(first, second),
+            (Key::String(first), Key::String(second)) => first.cmp(second),
+            _ => self.rank().cmp(&other.rank())
+        }
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Key) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Key) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Boolean(value) => value.fmt(f),
+            Key::Number(value) => value.fmt(f),
+            Key::String(value) => write!(f, "{:?}", value)
+        }
+    }
+}
+
+/// Pairs up the values a map literal evaluated to.
+pub(crate) fn from_items(items: Vec<Value>) -> Result<Map, Error> {
+    if items.len() % 2 == 1 {
+        let key = items.last().map(Value::to_string).unwrap_or_default();
+        return Err(Error::UnpairedMapKey(key));
+    }
+    let mut map = Map::new();
+    let mut items = items.into_iter();
+    while let (Some(key), Some(value)) = (items.next(), items.next()) {
+        map.insert(Key::try_from(key)?, value);
+    }
+    Ok(map)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MapWord {
+    New, Set, Get, Has, Remove, Keys, Values, For
+}
+
+pub(crate) const WORDS: &[(&str, MapWord)] = &[
+    ("map_new", MapWord::New), ("map_set", MapWord::Set), ("map_get", MapWord::Get),
+    ("map_has", MapWord::Has), ("map_remove", MapWord::Remove),
+    ("map_keys", MapWord::Keys), ("map_values", MapWord::Values), ("map_for", MapWord::For)
+];
+
+impl MapWord {
+    pub(crate) fn from_name(name: &str) -> Option<MapWord> {
+        WORDS.iter()
+            .find(|(word_name, _)| *word_name == name)
+            .map(|(_, function)| *function)
+    }
+}
+
+pub(crate) fn execute(function: MapWord, state: &mut State) -> Result<(), Error> {
+    let result = match function {
+        MapWord::New => Value::Map(Rc::new(Map::new())),
+        MapWord::Set => {
+            let value = state.pop()?;
+            let key = Key::try_from(state.pop()?)?;
+            let mut map = state.pop_map()?;
+            Rc::make_mut(&mut map).insert(key, value);
+            Value::Map(map)
+        },
+        MapWord::Get => {
+            let key = Key::try_from(state.pop()?)?;
+            let map = state.pop_map()?;
+            map.get(&key).cloned().ok_or_else(|| Error::MissingKey(key.to_string()))?
+        },
+        MapWord::Has => {
+            let key = Key::try_from(state.pop()?)?;
+            Value::Boolean(state.pop_map()?.contains_key(&key))
+        },
+        MapWord::Remove => {
+            let key = Key::try_from(state.pop()?)?;
+            let mut map = state.pop_map()?;
+            Rc::make_mut(&mut map).remove(&key);
+            Value::Map(map)
+        },
+        MapWord::Keys => {
+            let map = state.pop_map()?;
+            Value::Vector(Rc::new(map.keys().cloned().map(Key::into_value).collect()))
+        },
+        MapWord::Values => Value::Vector(Rc::new(state.pop_map()?.values().cloned().collect())),
+        MapWord::For => {
+            let function = state.pop_function()?;
+            for (key, value) in Rc::unwrap_or_clone(state.pop_map()?) {
+                state.stack.push(key.into_value());
+                state.stack.push(value);
+                state.run_block(&function)?;
+            }
+            return Ok(());
+        }
+    };
+    state.stack.push(result);
+    Ok(())
+}
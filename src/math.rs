@@ -0,0 +1,233 @@
+use std::{cmp::Ordering, f32, f64};
+
+use crate::{
+    bigint::BigInt,
+    decimal::RoundingMode,
+    error::Error,
+    number::{CastMode, Number},
+    runtime::State,
+    value::Value
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Math {
+    Pi, PiF32, E, EF32,
+    Sqrt, Sin, Cos, Tan, Asin, Acos, Atan, Atan2, Ln, Exp,
+    Abs, Floor, Ceil, Min, Max,
+    Pow, Isqrt, Gcd
+}
+
+pub(crate) const WORDS: &[(&str, Math)] = &[
+    ("pi_f64", Math::Pi), ("pi_f32", Math::PiF32), ("e_f64", Math::E), ("e_f32", Math::EF32),
+    ("sqrt", Math::Sqrt), ("sin", Math::Sin), ("cos", Math::Cos), ("tan", Math::Tan),
+    ("asin", Math::Asin), ("acos", Math::Acos), ("atan", Math::Atan), ("atan2", Math::Atan2),
+    ("ln", Math::Ln), ("exp", Math::Exp),
+    ("abs", Math::Abs), ("floor", Math::Floor), ("ceil", Math::Ceil), ("min", Math::Min), ("max", Math::Max),
+    ("pow", Math::Pow), ("isqrt", Math::Isqrt), ("gcd", Math::Gcd)
+];
+
+impl Math {
+    pub(crate) fn from_name(name: &str) -> Option<Math> {
+        WORDS.iter()
+            .find(|(word_name, _)| *word_name == name)
+            .map(|(_, function)| *function)
+    }
+}
+
+fn float_fn(value: Number, single: fn(f32) -> f32, double: fn(f64) -> f64) -> Result<Number, Error> {
+    match value {
+        Number::F32(value) => Ok(Number::F32(single(value))),
+        Number::F64(value) => Ok(Number::F64(double(value))),
+        value => float_fn(value.cast_to("f64", CastMode::Truncate)?, single, double)
+    }
+}
+
+fn atan2(y: Number, x: Number) -> Result<Number, Error> {
+    match (y, x) {
+        (Number::F32(y), Number::F32(x)) => Ok(Number::F32(y.atan2(x))),
+        (Number::F64(y), Number::F64(x)) => Ok(Number::F64(y.atan2(x))),
+        (y, x) => atan2(y.cast_to("f64", CastMode::Truncate)?, x.cast_to("f64", CastMode::Truncate)?)
+    }
+}
+
+macro_rules! signed_abs {
+    ($value:expr, $variant:ident) => {
+        $value.checked_abs().map(Number::$variant).ok_or(Error::ArithmeticOverflow { operation: "abs" })
+    }
+}
+
+fn abs(value: Number) -> Result<Number, Error> {
+    match value {
+        Number::I8(value) => signed_abs!(value, I8),
+        Number::I16(value) => signed_abs!(value, I16),
+        Number::I32(value) => signed_abs!(value, I32),
+        Number::I64(value) => signed_abs!(value, I64),
+        Number::I128(value) => signed_abs!(value, I128),
+        Number::F32(value) => Ok(Number::F32(value.abs())),
+        Number::F64(value) => Ok(Number::F64(value.abs())),
+        Number::BigInt(value) => Ok(Number::BigInt(value.abs())),
+        Number::Rational(value) => Ok(Number::Rational(value.abs())),
+        Number::Decimal(value) => signed_abs!(value, Decimal),
+        unsigned => Ok(unsigned)
+    }
+}
+
+fn is_nan(value: &Number) -> bool {
+    value.partial_cmp(value).is_none()
+}
+
+fn extremum(first: Number, second: Number, keep: Ordering) -> Result<Number, Error> {
+    let (first, second) = first.promote(second)?;
+    match first.partial_cmp(&second) {
+        Some(ordering) if ordering == keep.reverse() => Ok(second),
+        Some(_) => Ok(first),
+        None if is_nan(&first) => Ok(second),
+        None => Ok(first)
+    }
+}
+
+macro_rules! integer_pow {
+    ($value:expr, $exponent:expr, $variant:ident) => {
+        $value.checked_pow($exponent).map(Number::$variant).ok_or(Error::ArithmeticOverflow { operation: "pow" })
+    }
+}
+
+fn is_float(value: &Number) -> bool {
+    matches!(value, Number::F32(_) | Number::F64(_))
+}
+
+fn pow(base: Number, exponent: Number) -> Result<Number, Error> {
+    match (base, exponent) {
+        (Number::F32(base), Number::F32(exponent)) => Ok(Number::F32(base.powf(exponent))),
+        (Number::F32(base), exponent) if !is_float(&exponent) => {
+            Ok(Number::F32(base.powi(integer_exponent(exponent)?)))
+        },
+        (Number::F64(base), exponent) if !is_float(&exponent) => {
+            Ok(Number::F64(base.powi(integer_exponent(exponent)?)))
+        },
+        (base, exponent) if is_float(&base) || is_float(&exponent) => {
+            match (base.cast_to("f64", CastMode::Truncate)?, exponent.cast_to("f64", CastMode::Truncate)?) {
+                (Number::F64(base), Number::F64(exponent)) => Ok(Number::F64(base.powf(exponent))),
+                (base, _) => Err(Error::TypeMismatch { expected: "f64", found: base.type_name() })
+            }
+        },
+        (base, exponent) => {
+            let exponent = natural_exponent(exponent)?;
+            match base {
+                Number::I8(base) => integer_pow!(base, exponent, I8),
+                Number::I16(base) => integer_pow!(base, exponent, I16),
+                Number::I32(base) => integer_pow!(base, exponent, I32),
+                Number::I64(base) => integer_pow!(base, exponent, I64),
+                Number::I128(base) => integer_pow!(base, exponent, I128),
+                Number::U8(base) => integer_pow!(base, exponent, U8),
+                Number::U16(base) => integer_pow!(base, exponent, U16),
+                Number::U32(base) => integer_pow!(base, exponent, U32),
+                Number::U64(base) => integer_pow!(base, exponent, U64),
+                Number::U128(base) => integer_pow!(base, exponent, U128),
+                Number::BigInt(base) => Ok(Number::BigInt(base.pow(exponent))),
+                Number::Rational(base) => Ok(Number::Rational(base.pow(exponent))),
+                Number::Decimal(base) => integer_pow!(base, exponent, Decimal),
+                base => Err(Error::TypeMismatch { expected: "integer", found: base.type_name() })
+            }
+        }
+    }
+}
+
+fn integer_exponent(exponent: Number) -> Result<i32, Error> {
+    match exponent.cast_to("i32", CastMode::Checked)? {
+        Number::I32(exponent) => Ok(exponent),
+        exponent => Err(Error::TypeMismatch { expected: "i32", found: exponent.type_name() })
+    }
+}
+
+fn natural_exponent(exponent: Number) -> Result<u32, Error> {
+    match exponent {
+        Number::Rational(_) | Number::Decimal(_) => {
+            Err(Error::TypeMismatch { expected: "integer", found: exponent.type_name() })
+        },
+        exponent => match exponent.cast_to("u32", CastMode::Checked)? {
+            Number::U32(exponent) => Ok(exponent),
+            exponent => Err(Error::TypeMismatch { expected: "u32", found: exponent.type_name() })
+        }
+    }
+}
+
+macro_rules! signed_isqrt {
+    ($value:expr, $variant:ident) => {
+        $value.checked_isqrt()
+            .map(Number::$variant)
+            .ok_or_else(|| Error::OutOfDomain { operation: "isqrt", value: $value.to_string() })
+    }
+}
+
+fn isqrt(value: Number) -> Result<Number, Error> {
+    match value {
+        Number::I8(value) => signed_isqrt!(value, I8),
+        Number::I16(value) => signed_isqrt!(value, I16),
+        Number::I32(value) => signed_isqrt!(value, I32),
+        Number::I64(value) => signed_isqrt!(value, I64),
+        Number::I128(value) => signed_isqrt!(value, I128),
+        Number::U8(value) => Ok(Number::U8(value.isqrt())),
+        Number::U16(value) => Ok(Number::U16(value.isqrt())),
+        Number::U32(value) => Ok(Number::U32(value.isqrt())),
+        Number::U64(value) => Ok(Number::U64(value.isqrt())),
+        Number::U128(value) => Ok(Number::U128(value.isqrt())),
+        Number::BigInt(value) => value.isqrt()
+            .map(Number::BigInt)
+            .ok_or_else(|| Error::OutOfDomain { operation: "isqrt", value: value.to_string() }),
+        value => Err(Error::TypeMismatch { expected: "integer", found: value.type_name() })
+    }
+}
+
+fn gcd(first: Number, second: Number) -> Result<Number, Error> {
+    let (first, second) = first.promote(second)?;
+    let common = first.type_name();
+    let integer = |value: Number| match value {
+        Number::F32(_) | Number::F64(_) | Number::Rational(_) | Number::Decimal(_) => {
+            Err(Error::TypeMismatch { expected: "integer", found: value.type_name() })
+        },
+        value => match value.cast_to("big", CastMode::Checked)? {
+            Number::BigInt(value) => Ok(value),
+            value => Err(Error::TypeMismatch { expected: "big", found: value.type_name() })
+        }
+    };
+    let divisor: BigInt = integer(first)?.gcd(&integer(second)?);
+    Number::BigInt(divisor)
+        .cast_to(common, CastMode::Checked)
+        .map_err(|_| Error::ArithmeticOverflow { operation: "gcd" })
+}
+
+pub(crate) fn execute(function: Math, state: &mut State) -> Result<(), Error> {
+    let result = match function {
+        Math::Pi => Number::F64(f64::consts::PI),
+        Math::PiF32 => Number::F32(f32::consts::PI),
+        Math::E => Number::F64(f64::consts::E),
+        Math::EF32 => Number::F32(f32::consts::E),
+        Math::Sqrt => float_fn(state.pop_number()?, f32::sqrt, f64::sqrt)?,
+        Math::Sin => float_fn(state.pop_number()?, f32::sin, f64::sin)?,
+        Math::Cos => float_fn(state.pop_number()?, f32::cos, f64::cos)?,
+        Math::Tan => float_fn(state.pop_number()?, f32::tan, f64::tan)?,
+        Math::Asin => float_fn(state.pop_number()?, f32::asin, f64::asin)?,
+        Math::Acos => float_fn(state.pop_number()?, f32::acos, f64::acos)?,
+        Math::Atan => float_fn(state.pop_number()?, f32::atan, f64::atan)?,
+        Math::Ln => float_fn(state.pop_number()?, f32::ln, f64::ln)?,
+        Math::Exp => float_fn(state.pop_number()?, f32::exp, f64::exp)?,
+        Math::Abs => abs(state.pop_number()?)?,
+        Math::Floor => state.pop_number()?.round(0, RoundingMode::Floor)?,
+        Math::Ceil => state.pop_number()?.round(0, RoundingMode::Ceiling)?,
+        Math::Isqrt => isqrt(state.pop_number()?)?,
+        Math::Atan2 | Math::Min | Math::Max | Math::Pow | Math::Gcd => {
+            let second = state.pop_number()?;
+            let first = state.pop_number()?;
+            match function {
+                Math::Atan2 => atan2(first, second)?,
+                Math::Min => extremum(first, second, Ordering::Less)?,
+                Math::Max => extremum(first, second, Ordering::Greater)?,
+                Math::Pow => pow(first, second)?,
+                _ => gcd(first, second)?
+            }
+        }
+    };
+    state.stack.push(Value::Number(result));
+    Ok(())
+}
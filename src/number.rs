@@ -1,4 +1,14 @@
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+use std::fmt;
+
+use crate::{
+    bigint::BigInt,
+    decimal::{Decimal, RoundingMode},
+    error::Error,
+    lexer::number_literal,
+    rational::Rational
+};
+
+#[derive(Debug, Clone)]
 pub enum Number {
     I8(i8),
     I16(i16),
@@ -12,23 +22,94 @@ pub enum Number {
     U128(u128),
     F32(f32),
     F64(f64),
+    BigInt(BigInt),
+    Rational(Rational),
+    Decimal(Decimal),
 }
 
 macro_rules! fallible_cast {
     ($val:expr, $cast_to:ident, $dest_t:ident) => {
         Ok(Number::$cast_to((*$val)
             .try_into()
-            .map_err(|_| format!(
-                "Error casting '{}' to '{}'",
-                $val,
-                stringify!($dest_t)
-            ))?)
+            .map_err(|_| Error::InvalidCast {
+                value: $val.to_string(),
+                target: stringify!($dest_t).to_string()
+            })?)
         )
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastMode {
+    Truncate,
+    Checked
+}
+
+fn exact_integer<T: TryFrom<i128> + TryFrom<u128>>(value: f64) -> Option<T> {
+    if !value.is_finite() || value.fract() != 0.0 {
+        None
+    } else if value < 0.0 {
+        if value < i128::MIN as f64 { None } else { T::try_from(value as i128).ok() }
+    } else if value >= 2f64.powi(128) {
+        None
+    } else {
+        T::try_from(value as u128).ok()
+    }
+}
+
+fn big_to_int<T: TryFrom<i128> + TryFrom<u128>>(value: &BigInt) -> Option<T> {
+    value.to_i128().and_then(|value| T::try_from(value).ok())
+        .or_else(|| value.to_u128().and_then(|value| T::try_from(value).ok()))
+}
+
+fn rational_to_big(value: &Rational, mode: CastMode) -> Option<BigInt> {
+    match mode {
+        CastMode::Truncate => Some(value.trunc()),
+        CastMode::Checked => value.is_integer().then(|| value.numerator().clone())
+    }
+}
+
+fn decimal_to_int<T: TryFrom<i128>>(value: &Decimal, mode: CastMode) -> Option<T> {
+    if mode == CastMode::Checked && !value.is_integer() {
+        None
+    } else {
+        T::try_from(value.trunc()).ok()
+    }
+}
+
+fn float_to_big(value: f64, mode: CastMode) -> Option<BigInt> {
+    if mode == CastMode::Checked && value.fract() != 0.0 {
+        None
+    } else {
+        BigInt::from_f64(value)
+    }
+}
+
+macro_rules! exact_to_int {
+    ($result:expr, $val:expr, $cast_to:ident, $dest_t:ident) => {
+        $result.map(Number::$cast_to).ok_or_else(|| Error::InvalidCast {
+            value: $val.to_string(),
+            target: stringify!($dest_t).to_string()
+        })
+    }
+}
+
+macro_rules! float_to_int {
+    ($val:expr, $mode:expr, $cast_to:ident, $dest_t:ident) => {
+        match $mode {
+            CastMode::Truncate => Ok(Number::$cast_to(*$val as $dest_t)),
+            CastMode::Checked => exact_integer(*$val as f64)
+                .map(Number::$cast_to)
+                .ok_or_else(|| Error::InvalidCast {
+                    value: $val.to_string(),
+                    target: stringify!($dest_t).to_string()
+                })
+        }
+    }
+}
+
 macro_rules! cast_block {
-    ($obj:expr, $variant:ident, $result_type:ident) => {
+    ($obj:expr, $mode:expr, $variant:ident, $result_type:ident) => {
         match $obj {
             Number::I8(value) => fallible_cast!(value, $variant, $result_type),
             Number::I16(value) => fallible_cast!(value, $variant, $result_type),
@@ -40,12 +121,24 @@ macro_rules! cast_block {
             Number::U32(value) => fallible_cast!(value, $variant, $result_type),
             Number::U64(value) => fallible_cast!(value, $variant, $result_type),
             Number::U128(value) => fallible_cast!(value, $variant, $result_type),
-            Number::F32(value) => Ok(Number::$variant(*value as $result_type)),
-            Number::F64(value) => Ok(Number::$variant(*value as $result_type)),
+            Number::F32(value) => float_to_int!(value, $mode, $variant, $result_type),
+            Number::F64(value) => float_to_int!(value, $mode, $variant, $result_type),
+            Number::BigInt(value) => exact_to_int!(big_to_int(value), value, $variant, $result_type),
+            Number::Rational(value) => exact_to_int!(
+                rational_to_big(value, $mode).and_then(|value| big_to_int(&value)),
+                value, $variant, $result_type
+            ),
+            Number::Decimal(value) => exact_to_int!(decimal_to_int(value, $mode), value, $variant, $result_type),
         }
     }
 }
 
+macro_rules! parse_as {
+    ($text:expr, $variant:ident, $result_type:ident) => {
+        $text.parse::<$result_type>().ok().map(Number::$variant)
+    }
+}
+
 macro_rules! float_cast_block {
     ($obj:expr, $variant:ident, $result_type:ident) => {
         match $obj {
@@ -61,126 +154,664 @@ macro_rules! float_cast_block {
             Number::U128(value) => Ok(Number::$variant(*value as $result_type)),
             Number::F32(value) => Ok(Number::$variant(*value as $result_type)),
             Number::F64(value) => Ok(Number::$variant(*value as $result_type)),
+            Number::BigInt(value) => Ok(Number::$variant(value.to_f64() as $result_type)),
+            Number::Rational(value) => Ok(Number::$variant(value.to_f64() as $result_type)),
+            Number::Decimal(value) => Ok(Number::$variant(value.to_f64() as $result_type)),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Signed,
+    Unsigned,
+    Float,
+    Big,
+    Ratio,
+    Decimal
+}
+
+macro_rules! number_op {
+    ($v1:expr, $v2:expr, $int:expr, $float:expr) => {
+        match Number::promote($v1, $v2)? {
+            (Number::I8(v1), Number::I8(v2)) => ($int)(v1, v2).map(Number::I8),
+            (Number::I16(v1), Number::I16(v2)) => ($int)(v1, v2).map(Number::I16),
+            (Number::I32(v1), Number::I32(v2)) => ($int)(v1, v2).map(Number::I32),
+            (Number::I64(v1), Number::I64(v2)) => ($int)(v1, v2).map(Number::I64),
+            (Number::I128(v1), Number::I128(v2)) => ($int)(v1, v2).map(Number::I128),
+            (Number::U8(v1), Number::U8(v2)) => ($int)(v1, v2).map(Number::U8),
+            (Number::U16(v1), Number::U16(v2)) => ($int)(v1, v2).map(Number::U16),
+            (Number::U32(v1), Number::U32(v2)) => ($int)(v1, v2).map(Number::U32),
+            (Number::U64(v1), Number::U64(v2)) => ($int)(v1, v2).map(Number::U64),
+            (Number::U128(v1), Number::U128(v2)) => ($int)(v1, v2).map(Number::U128),
+            (Number::F32(v1), Number::F32(v2)) => Ok(Number::F32(($float)(v1, v2))),
+            (Number::F64(v1), Number::F64(v2)) => Ok(Number::F64(($float)(v1, v2))),
+            (Number::BigInt(v1), Number::BigInt(v2)) => ($int)(v1, v2).map(Number::BigInt),
+            (Number::Rational(v1), Number::Rational(v2)) => ($int)(v1, v2).map(Number::Rational),
+            (Number::Decimal(v1), Number::Decimal(v2)) => ($int)(v1, v2).map(Number::Decimal),
+            (v1, v2) => Err(Error::TypeMismatch { expected: v1.type_name(), found: v2.type_name() })
+        }
+    }
+}
+
+macro_rules! bitwise_op {
+    ($v1:expr, $v2:expr, $op:tt) => {
+        match Number::promote($v1, $v2)? {
+            (Number::I8(v1), Number::I8(v2)) => Ok(Number::I8(v1 $op v2)),
+            (Number::I16(v1), Number::I16(v2)) => Ok(Number::I16(v1 $op v2)),
+            (Number::I32(v1), Number::I32(v2)) => Ok(Number::I32(v1 $op v2)),
+            (Number::I64(v1), Number::I64(v2)) => Ok(Number::I64(v1 $op v2)),
+            (Number::I128(v1), Number::I128(v2)) => Ok(Number::I128(v1 $op v2)),
+            (Number::U8(v1), Number::U8(v2)) => Ok(Number::U8(v1 $op v2)),
+            (Number::U16(v1), Number::U16(v2)) => Ok(Number::U16(v1 $op v2)),
+            (Number::U32(v1), Number::U32(v2)) => Ok(Number::U32(v1 $op v2)),
+            (Number::U64(v1), Number::U64(v2)) => Ok(Number::U64(v1 $op v2)),
+            (Number::U128(v1), Number::U128(v2)) => Ok(Number::U128(v1 $op v2)),
+            (number, _) => Err(Error::TypeMismatch { expected: "integer", found: number.type_name() })
         }
     }
 }
 
+macro_rules! integer_map {
+    ($number:expr, $operation:expr, $value:ident => $body:expr) => {
+        match $number {
+            Number::I8($value) => $body.map(Number::I8),
+            Number::I16($value) => $body.map(Number::I16),
+            Number::I32($value) => $body.map(Number::I32),
+            Number::I64($value) => $body.map(Number::I64),
+            Number::I128($value) => $body.map(Number::I128),
+            Number::U8($value) => $body.map(Number::U8),
+            Number::U16($value) => $body.map(Number::U16),
+            Number::U32($value) => $body.map(Number::U32),
+            Number::U64($value) => $body.map(Number::U64),
+            Number::U128($value) => $body.map(Number::U128),
+            number => return Err(Error::TypeMismatch { expected: "integer", found: number.type_name() })
+        }.ok_or(Error::ArithmeticOverflow { operation: $operation })
+    }
+}
+
+macro_rules! integer_count {
+    ($value:expr, $method:ident) => {
+        match $value {
+            Number::I8(value) => Ok(Number::U32(value.$method())),
+            Number::I16(value) => Ok(Number::U32(value.$method())),
+            Number::I32(value) => Ok(Number::U32(value.$method())),
+            Number::I64(value) => Ok(Number::U32(value.$method())),
+            Number::I128(value) => Ok(Number::U32(value.$method())),
+            Number::U8(value) => Ok(Number::U32(value.$method())),
+            Number::U16(value) => Ok(Number::U32(value.$method())),
+            Number::U32(value) => Ok(Number::U32(value.$method())),
+            Number::U64(value) => Ok(Number::U32(value.$method())),
+            Number::U128(value) => Ok(Number::U32(value.$method())),
+            number => Err(Error::TypeMismatch { expected: "integer", found: number.type_name() })
+        }
+    }
+}
+
+macro_rules! checked_op {
+    ($v1:expr, $v2:expr, $method:ident, $op:tt) => {
+        number_op!(
+            $v1, $v2,
+            |v1, v2| CheckedOps::$method(v1, v2).ok_or(Error::ArithmeticOverflow { operation: stringify!($op) }),
+            |v1, v2| v1 $op v2
+        )
+    }
+}
+
+macro_rules! checked_division {
+    ($v1:expr, $v2:expr, $method:ident, $op:tt) => {
+        number_op!(
+            $v1, $v2,
+            |v1, v2| if CheckedOps::is_zero(&v2) {
+                Err(Error::DivisionByZero)
+            } else {
+                CheckedOps::$method(v1, v2).ok_or(Error::ArithmeticOverflow { operation: stringify!($op) })
+            },
+            |v1, v2| v1 $op v2
+        )
+    }
+}
+
+macro_rules! unchecked_op {
+    ($v1:expr, $v2:expr, $method:ident, $op:tt) => {
+        number_op!($v1, $v2, |v1, v2| Ok(CheckedOps::$method(v1, v2)), |v1, v2| v1 $op v2)
+    }
+}
+
+macro_rules! overflowing_op {
+    ($v1:expr, $v2:expr, $method:ident, $op:tt) => {
+        {
+            let mut overflowed = false;
+            let result = number_op!(
+                $v1, $v2,
+                |v1, v2| {
+                    let (result, overflow) = CheckedOps::$method(v1, v2);
+                    overflowed = overflow;
+                    Ok(result)
+                },
+                |v1, v2| v1 $op v2
+            )?;
+            Ok((result, overflowed))
+        }
+    }
+}
+
+trait CheckedOps: Sized {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn checked_rem(self, other: Self) -> Option<Self>;
+    fn wrapping_add(self, other: Self) -> Self;
+    fn wrapping_sub(self, other: Self) -> Self;
+    fn wrapping_mul(self, other: Self) -> Self;
+    fn saturating_add(self, other: Self) -> Self;
+    fn saturating_sub(self, other: Self) -> Self;
+    fn saturating_mul(self, other: Self) -> Self;
+    fn overflowing_add(self, other: Self) -> (Self, bool);
+    fn overflowing_sub(self, other: Self) -> (Self, bool);
+    fn overflowing_mul(self, other: Self) -> (Self, bool);
+    fn is_zero(&self) -> bool;
+}
+
+macro_rules! impl_checked_ops {
+    ($($int:ty),*) => {
+        $(impl CheckedOps for $int {
+            fn checked_add(self, other: Self) -> Option<Self> { <$int>::checked_add(self, other) }
+            fn checked_sub(self, other: Self) -> Option<Self> { <$int>::checked_sub(self, other) }
+            fn checked_mul(self, other: Self) -> Option<Self> { <$int>::checked_mul(self, other) }
+            fn checked_div(self, other: Self) -> Option<Self> { <$int>::checked_div(self, other) }
+            fn checked_rem(self, other: Self) -> Option<Self> { <$int>::checked_rem(self, other) }
+            fn wrapping_add(self, other: Self) -> Self { <$int>::wrapping_add(self, other) }
+            fn wrapping_sub(self, other: Self) -> Self { <$int>::wrapping_sub(self, other) }
+            fn wrapping_mul(self, other: Self) -> Self { <$int>::wrapping_mul(self, other) }
+            fn saturating_add(self, other: Self) -> Self { <$int>::saturating_add(self, other) }
+            fn saturating_sub(self, other: Self) -> Self { <$int>::saturating_sub(self, other) }
+            fn saturating_mul(self, other: Self) -> Self { <$int>::saturating_mul(self, other) }
+            fn overflowing_add(self, other: Self) -> (Self, bool) { <$int>::overflowing_add(self, other) }
+            fn overflowing_sub(self, other: Self) -> (Self, bool) { <$int>::overflowing_sub(self, other) }
+            fn overflowing_mul(self, other: Self) -> (Self, bool) { <$int>::overflowing_mul(self, other) }
+            fn is_zero(&self) -> bool { *self == 0 }
+        })*
+    }
+}
+
+impl_checked_ops!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+macro_rules! impl_exact_ops {
+    ($($exact:ty),*) => {
+        $(impl CheckedOps for $exact {
+            fn checked_add(self, other: Self) -> Option<Self> { Some(&self + &other) }
+            fn checked_sub(self, other: Self) -> Option<Self> { Some(&self - &other) }
+            fn checked_mul(self, other: Self) -> Option<Self> { Some(&self * &other) }
+            fn checked_div(self, other: Self) -> Option<Self> { <$exact>::checked_div(&self, &other) }
+            fn checked_rem(self, other: Self) -> Option<Self> { <$exact>::checked_rem(&self, &other) }
+            fn wrapping_add(self, other: Self) -> Self { &self + &other }
+            fn wrapping_sub(self, other: Self) -> Self { &self - &other }
+            fn wrapping_mul(self, other: Self) -> Self { &self * &other }
+            fn saturating_add(self, other: Self) -> Self { &self + &other }
+            fn saturating_sub(self, other: Self) -> Self { &self - &other }
+            fn saturating_mul(self, other: Self) -> Self { &self * &other }
+            fn overflowing_add(self, other: Self) -> (Self, bool) { (&self + &other, false) }
+            fn overflowing_sub(self, other: Self) -> (Self, bool) { (&self - &other, false) }
+            fn overflowing_mul(self, other: Self) -> (Self, bool) { (&self * &other, false) }
+            fn is_zero(&self) -> bool { <$exact>::is_zero(self) }
+        })*
+    }
+}
+
+impl_exact_ops!(BigInt, Rational);
+
+impl CheckedOps for Decimal {
+    fn checked_add(self, other: Self) -> Option<Self> { Decimal::checked_add(&self, &other) }
+    fn checked_sub(self, other: Self) -> Option<Self> { Decimal::checked_sub(&self, &other) }
+    fn checked_mul(self, other: Self) -> Option<Self> { Decimal::checked_mul(&self, &other) }
+    fn checked_div(self, other: Self) -> Option<Self> { Decimal::checked_div(&self, &other, RoundingMode::default()) }
+    fn checked_rem(self, other: Self) -> Option<Self> { Decimal::checked_rem(&self, &other) }
+    fn wrapping_add(self, other: Self) -> Self { self.combine(&other, i128::wrapping_add) }
+    fn wrapping_sub(self, other: Self) -> Self { self.combine(&other, i128::wrapping_sub) }
+    fn wrapping_mul(self, other: Self) -> Self { self.multiply(&other, i128::wrapping_mul) }
+    fn saturating_add(self, other: Self) -> Self { self.combine(&other, i128::saturating_add) }
+    fn saturating_sub(self, other: Self) -> Self { self.combine(&other, i128::saturating_sub) }
+    fn saturating_mul(self, other: Self) -> Self { self.multiply(&other, i128::saturating_mul) }
+    fn overflowing_add(self, other: Self) -> (Self, bool) {
+        (self.combine(&other, i128::wrapping_add), Decimal::checked_add(&self, &other).is_none())
+    }
+    fn overflowing_sub(self, other: Self) -> (Self, bool) {
+        (self.combine(&other, i128::wrapping_sub), Decimal::checked_sub(&self, &other).is_none())
+    }
+    fn overflowing_mul(self, other: Self) -> (Self, bool) {
+        (self.multiply(&other, i128::wrapping_mul), Decimal::checked_mul(&self, &other).is_none())
+    }
+    fn is_zero(&self) -> bool { Decimal::is_zero(self) }
+}
+
 impl Number {
-    pub fn cast_to(&self, result_type: String) -> Result<Number, String> {
-        match result_type.as_str() {
-            "i8" => cast_block!(self, I8, i8),
-            "i16" => cast_block!(self, I16, i16),
-            "i32" => cast_block!(self, I32, i32),
-            "i64" => cast_block!(self, I64, i64),
-            "i128" => cast_block!(self, I128, i128),
-            "u8" => cast_block!(self, U8, u8),
-            "u16" => cast_block!(self, U16, u16),
-            "u32" => cast_block!(self, U32, u32),
-            "u64" => cast_block!(self, U64, u64),
-            "u128" => cast_block!(self, U128, u128),
+    fn kind(&self) -> (Kind, u32) {
+        match self {
+            Number::I8(_) => (Kind::Signed, 8),
+            Number::I16(_) => (Kind::Signed, 16),
+            Number::I32(_) => (Kind::Signed, 32),
+            Number::I64(_) => (Kind::Signed, 64),
+            Number::I128(_) => (Kind::Signed, 128),
+            Number::U8(_) => (Kind::Unsigned, 8),
+            Number::U16(_) => (Kind::Unsigned, 16),
+            Number::U32(_) => (Kind::Unsigned, 32),
+            Number::U64(_) => (Kind::Unsigned, 64),
+            Number::U128(_) => (Kind::Unsigned, 128),
+            Number::F32(_) => (Kind::Float, 32),
+            Number::F64(_) => (Kind::Float, 64),
+            Number::BigInt(_) => (Kind::Big, 256),
+            Number::Rational(_) => (Kind::Ratio, 256),
+            Number::Decimal(_) => (Kind::Decimal, 128),
+        }
+    }
+
+    /// The type both operands of a mixed-type operation are converted to:
+    ///
+    /// - integers of the same signedness widen to the larger width;
+    /// - an unsigned and a signed integer meet at the smallest signed type
+    ///   holding both (`u8`+`i8` is `i16`, `u32`+`i64` is `i64`); `u128`
+    ///   and a signed integer meet at `big`;
+    /// - any integer meets `big` at `big`, and any integer or `big` meets
+    ///   `ratio` at `ratio`;
+    /// - floats widen to the larger float, and an integer meets a float at
+    ///   `f32` only if the integer is 16 bits or less, otherwise at `f64`;
+    ///   `big` and `ratio` meet a float at `f64`;
+    /// - `dec` absorbs any fixed-width integer, meets `big` or `ratio` at
+    ///   `ratio`, and has no common type with a float.
+    pub fn common_type(&self, other: &Number) -> Option<&'static str> {
+        let (kind, bits) = match (self.kind(), other.kind()) {
+            ((kind @ Kind::Signed, b1), (Kind::Signed, b2))
+            | ((kind @ Kind::Unsigned, b1), (Kind::Unsigned, b2))
+            | ((kind @ Kind::Float, b1), (Kind::Float, b2))
+            | ((kind @ Kind::Big, b1), (Kind::Big, b2))
+            | ((kind @ Kind::Ratio, b1), (Kind::Ratio, b2))
+            | ((kind @ Kind::Decimal, b1), (Kind::Decimal, b2)) => (kind, b1.max(b2)),
+            ((Kind::Decimal, _), (Kind::Float, _)) | ((Kind::Float, _), (Kind::Decimal, _)) => return None,
+            ((Kind::Float, float), (_, int)) | ((_, int), (Kind::Float, float)) => {
+                (Kind::Float, if int <= 16 { float } else { 64 })
+            },
+            ((Kind::Ratio, bits), _) | (_, (Kind::Ratio, bits)) => (Kind::Ratio, bits),
+            ((Kind::Decimal, _), (Kind::Big, bits)) | ((Kind::Big, bits), (Kind::Decimal, _)) => (Kind::Ratio, bits),
+            ((Kind::Big, bits), _) | (_, (Kind::Big, bits)) => (Kind::Big, bits),
+            ((Kind::Decimal, bits), _) | (_, (Kind::Decimal, bits)) => (Kind::Decimal, bits),
+            ((Kind::Signed, signed), (_, unsigned)) | ((_, unsigned), (Kind::Signed, signed)) => {
+                if signed > unsigned {
+                    (Kind::Signed, signed)
+                } else if unsigned < 128 {
+                    (Kind::Signed, unsigned * 2)
+                } else {
+                    (Kind::Big, 256)
+                }
+            },
+        };
+        Some(match (kind, bits) {
+            (Kind::Signed, 8) => "i8",
+            (Kind::Signed, 16) => "i16",
+            (Kind::Signed, 32) => "i32",
+            (Kind::Signed, 64) => "i64",
+            (Kind::Signed, _) => "i128",
+            (Kind::Unsigned, 8) => "u8",
+            (Kind::Unsigned, 16) => "u16",
+            (Kind::Unsigned, 32) => "u32",
+            (Kind::Unsigned, 64) => "u64",
+            (Kind::Unsigned, _) => "u128",
+            (Kind::Float, 32) => "f32",
+            (Kind::Float, _) => "f64",
+            (Kind::Big, _) => "big",
+            (Kind::Ratio, _) => "ratio",
+            (Kind::Decimal, _) => "dec",
+        })
+    }
+
+    pub fn promote(self, other: Number) -> Result<(Number, Number), Error> {
+        if self.same_type(&other) {
+            return Ok((self, other));
+        }
+        let common = self.common_type(&other)
+            .ok_or(Error::TypeMismatch { expected: self.type_name(), found: other.type_name() })?;
+        Ok((self.cast_to(common, CastMode::Truncate)?, other.cast_to(common, CastMode::Truncate)?))
+    }
+
+    /// Compares the variants directly, which is cheaper than comparing
+    /// `type_name`s on the arithmetic hot path.
+    pub fn same_type(&self, other: &Number) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Number::I8(_) => "i8",
+            Number::I16(_) => "i16",
+            Number::I32(_) => "i32",
+            Number::I64(_) => "i64",
+            Number::I128(_) => "i128",
+            Number::U8(_) => "u8",
+            Number::U16(_) => "u16",
+            Number::U32(_) => "u32",
+            Number::U64(_) => "u64",
+            Number::U128(_) => "u128",
+            Number::F32(_) => "f32",
+            Number::F64(_) => "f64",
+            Number::BigInt(_) => "big",
+            Number::Rational(_) => "ratio",
+            Number::Decimal(_) => "dec",
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self == &Number::U8(0)
+    }
+
+    pub fn cast_to(&self, result_type: &str, mode: CastMode) -> Result<Number, Error> {
+        match result_type {
+            "i8" => cast_block!(self, mode, I8, i8),
+            "i16" => cast_block!(self, mode, I16, i16),
+            "i32" => cast_block!(self, mode, I32, i32),
+            "i64" => cast_block!(self, mode, I64, i64),
+            "i128" => cast_block!(self, mode, I128, i128),
+            "u8" => cast_block!(self, mode, U8, u8),
+            "u16" => cast_block!(self, mode, U16, u16),
+            "u32" => cast_block!(self, mode, U32, u32),
+            "u64" => cast_block!(self, mode, U64, u64),
+            "u128" => cast_block!(self, mode, U128, u128),
             "f32" => float_cast_block!(self, F32, f32),
-            _ => Err(format!("Unknown number type {}", result_type))
+            "f64" => float_cast_block!(self, F64, f64),
+            "big" => self.to_big(mode).map(Number::BigInt),
+            "ratio" => self.to_rational().map(Number::Rational),
+            "dec" => self.to_decimal(mode).map(Number::Decimal),
+            _ => Err(Error::UnknownType(result_type.to_string()))
+        }
+    }
+
+    pub fn parse(text: &str, result_type: &str, mode: CastMode) -> Result<Number, Error> {
+        let invalid = || Error::InvalidCast { value: text.to_string(), target: result_type.to_string() };
+        let direct = match result_type {
+            "i8" => parse_as!(text, I8, i8),
+            "i16" => parse_as!(text, I16, i16),
+            "i32" => parse_as!(text, I32, i32),
+            "i64" => parse_as!(text, I64, i64),
+            "i128" => parse_as!(text, I128, i128),
+            "u8" => parse_as!(text, U8, u8),
+            "u16" => parse_as!(text, U16, u16),
+            "u32" => parse_as!(text, U32, u32),
+            "u64" => parse_as!(text, U64, u64),
+            "u128" => parse_as!(text, U128, u128),
+            "f32" => parse_as!(text, F32, f32),
+            "f64" => parse_as!(text, F64, f64),
+            "big" => BigInt::parse(text).map(Number::BigInt),
+            "ratio" => Rational::parse(text).map(Number::Rational),
+            "dec" => Decimal::parse(text).map(Number::Decimal),
+            _ => return Err(Error::UnknownType(result_type.to_string()))
+        };
+        if let Some(number) = direct {
+            return Ok(number);
+        }
+        match number_literal(text.trim()) {
+            Some(Ok(number)) => number.cast_to(result_type, mode),
+            _ => Err(invalid())
+        }
+    }
+
+    fn to_big(&self, mode: CastMode) -> Result<BigInt, Error> {
+        let invalid = || Error::InvalidCast { value: self.to_string(), target: "big".to_string() };
+        match self {
+            Number::I8(value) => Ok(BigInt::from(*value as i128)),
+            Number::I16(value) => Ok(BigInt::from(*value as i128)),
+            Number::I32(value) => Ok(BigInt::from(*value as i128)),
+            Number::I64(value) => Ok(BigInt::from(*value as i128)),
+            Number::I128(value) => Ok(BigInt::from(*value)),
+            Number::U8(value) => Ok(BigInt::from(*value as u128)),
+            Number::U16(value) => Ok(BigInt::from(*value as u128)),
+            Number::U32(value) => Ok(BigInt::from(*value as u128)),
+            Number::U64(value) => Ok(BigInt::from(*value as u128)),
+            Number::U128(value) => Ok(BigInt::from(*value)),
+            Number::F32(value) => float_to_big(*value as f64, mode).ok_or_else(invalid),
+            Number::F64(value) => float_to_big(*value, mode).ok_or_else(invalid),
+            Number::BigInt(value) => Ok(value.clone()),
+            Number::Rational(value) => rational_to_big(value, mode).ok_or_else(invalid),
+            Number::Decimal(value) => if mode == CastMode::Checked && !value.is_integer() {
+                Err(invalid())
+            } else {
+                Ok(BigInt::from(value.trunc()))
+            },
+        }
+    }
+
+    fn to_rational(&self) -> Result<Rational, Error> {
+        let invalid = || Error::InvalidCast { value: self.to_string(), target: "ratio".to_string() };
+        match self {
+            Number::F32(value) => Rational::from_f64(*value as f64).ok_or_else(invalid),
+            Number::F64(value) => Rational::from_f64(*value).ok_or_else(invalid),
+            Number::Rational(value) => Ok(value.clone()),
+            Number::Decimal(value) => Ok(value.to_rational()),
+            _ => self.to_big(CastMode::Checked).map(Rational::from_integer),
+        }
+    }
+
+    fn to_decimal(&self, mode: CastMode) -> Result<Decimal, Error> {
+        let invalid = || Error::InvalidCast { value: self.to_string(), target: "dec".to_string() };
+        match self {
+            Number::F32(value) => Decimal::from_float_text(&value.to_string()).ok_or_else(invalid),
+            Number::F64(value) => Decimal::from_float_text(&value.to_string()).ok_or_else(invalid),
+            Number::Rational(value) => match Decimal::from_rational(value) {
+                Some((decimal, exact)) if exact || mode == CastMode::Truncate => Ok(decimal),
+                _ => Err(invalid())
+            },
+            Number::Decimal(value) => Ok(*value),
+            _ => self.to_big(CastMode::Checked)?.to_i128().map(Decimal::from_integer).ok_or_else(invalid),
+        }
+    }
+
+    /// Division that rounds decimal results with `mode`; every other type
+    /// divides as `/` does.
+    pub fn divide(self, other: Number, mode: RoundingMode) -> Result<Number, Error> {
+        match self.promote(other)? {
+            (Number::Decimal(_), Number::Decimal(v2)) if v2.is_zero() => Err(Error::DivisionByZero),
+            (Number::Decimal(v1), Number::Decimal(v2)) => Decimal::checked_div(&v1, &v2, mode)
+                .map(Number::Decimal)
+                .ok_or(Error::ArithmeticOverflow { operation: "/" }),
+            (v1, v2) => v1 / v2
+        }
+    }
+
+    /// Rounds to `places` digits after the decimal point. Decimals take that
+    /// many places as their new scale; integers are already whole.
+    pub fn round(self, places: u32, mode: RoundingMode) -> Result<Number, Error> {
+        let overflow = Error::ArithmeticOverflow { operation: "round" };
+        match self {
+            Number::F32(value) => {
+                let unit = 10f64.powi(places as i32);
+                Ok(Number::F32((mode.round_float(value as f64 * unit) / unit) as f32))
+            },
+            Number::F64(value) => {
+                let unit = 10f64.powi(places as i32);
+                Ok(Number::F64(mode.round_float(value * unit) / unit))
+            },
+            Number::Rational(value) => {
+                let (scaled, _) = value.round_to(places, mode);
+                Rational::new(scaled, BigInt::from(10u128).pow(places))
+                    .map(Number::Rational)
+                    .ok_or(overflow)
+            },
+            Number::Decimal(value) => value.rescale(places, mode).map(Number::Decimal).ok_or(overflow),
+            integer => Ok(integer)
         }
     }
 }
 
-impl std::ops::Add for Number {
-    type Output = Result<Number, String>;
-    fn add(self, other: Number) -> Result<Number, String> {
-        match (self, other) {
-            (Number::I8(v1), Number::I8(v2)) => Ok(Number::I8(v1+v2)),
-            (Number::I16(v1), Number::I16(v2)) => Ok(Number::I16(v1+v2)),
-            (Number::I32(v1), Number::I32(v2)) => Ok(Number::I32(v1+v2)),
-            (Number::I64(v1), Number::I64(v2)) => Ok(Number::I64(v1+v2)),
-            (Number::I128(v1), Number::I128(v2)) => Ok(Number::I128(v1+v2)),
-            (Number::U8(v1), Number::U8(v2)) => Ok(Number::U8(v1+v2)),
-            (Number::U16(v1), Number::U16(v2)) => Ok(Number::U16(v1+v2)),
-            (Number::U32(v1), Number::U32(v2)) => Ok(Number::U32(v1+v2)),
-            (Number::U64(v1), Number::U64(v2)) => Ok(Number::U64(v1+v2)),
-            (Number::U128(v1), Number::U128(v2)) => Ok(Number::U128(v1+v2)),
-            (Number::F32(v1), Number::F32(v2)) => Ok(Number::F32(v1+v2)),
-            (Number::F64(v1), Number::F64(v2)) => Ok(Number::F64(v1+v2)),
-            _ => Err("Mismatched number types for '+'".to_string())
+
+impl Number {
+    pub fn wrapping_add(self, other: Number) -> Result<Number, Error> {
+        unchecked_op!(self, other, wrapping_add, +)
+    }
+    pub fn wrapping_sub(self, other: Number) -> Result<Number, Error> {
+        unchecked_op!(self, other, wrapping_sub, -)
+    }
+    pub fn wrapping_mul(self, other: Number) -> Result<Number, Error> {
+        unchecked_op!(self, other, wrapping_mul, *)
+    }
+    pub fn saturating_add(self, other: Number) -> Result<Number, Error> {
+        unchecked_op!(self, other, saturating_add, +)
+    }
+    pub fn saturating_sub(self, other: Number) -> Result<Number, Error> {
+        unchecked_op!(self, other, saturating_sub, -)
+    }
+    pub fn saturating_mul(self, other: Number) -> Result<Number, Error> {
+        unchecked_op!(self, other, saturating_mul, *)
+    }
+    pub fn overflowing_add(self, other: Number) -> Result<(Number, bool), Error> {
+        overflowing_op!(self, other, overflowing_add, +)
+    }
+    pub fn overflowing_sub(self, other: Number) -> Result<(Number, bool), Error> {
+        overflowing_op!(self, other, overflowing_sub, -)
+    }
+    pub fn overflowing_mul(self, other: Number) -> Result<(Number, bool), Error> {
+        overflowing_op!(self, other, overflowing_mul, *)
+    }
+}
+
+impl Number {
+    pub fn bit_and(self, other: Number) -> Result<Number, Error> {
+        bitwise_op!(self, other, &)
+    }
+    pub fn bit_or(self, other: Number) -> Result<Number, Error> {
+        bitwise_op!(self, other, |)
+    }
+    pub fn bit_xor(self, other: Number) -> Result<Number, Error> {
+        bitwise_op!(self, other, ^)
+    }
+    pub fn bit_not(self) -> Result<Number, Error> {
+        integer_map!(self, "not", value => Some(!value))
+    }
+    fn bit_width(&self) -> Result<u32, Error> {
+        match self {
+            Number::I8(_) | Number::U8(_) => Ok(8),
+            Number::I16(_) | Number::U16(_) => Ok(16),
+            Number::I32(_) | Number::U32(_) => Ok(32),
+            Number::I64(_) | Number::U64(_) => Ok(64),
+            Number::I128(_) | Number::U128(_) => Ok(128),
+            number => Err(Error::TypeMismatch { expected: "integer", found: number.type_name() })
         }
     }
+    /// A shift amount must be a whole number from zero up to, but not
+    /// including, the bit width of the type being shifted.
+    fn shift_amount(&self, amount: &Number) -> Result<u32, Error> {
+        let bits = self.bit_width()?;
+        match amount.cast_to("big", CastMode::Checked)?.cast_to("u32", CastMode::Checked) {
+            Ok(Number::U32(amount)) if amount < bits => Ok(amount),
+            _ => Err(Error::ShiftOutOfRange { amount: amount.to_string(), bits })
+        }
+    }
+    pub fn shift_left(self, amount: Number) -> Result<Number, Error> {
+        let amount = self.shift_amount(&amount)?;
+        integer_map!(self, "shl", value => value.checked_shl(amount))
+    }
+    pub fn shift_right(self, amount: Number) -> Result<Number, Error> {
+        let amount = self.shift_amount(&amount)?;
+        integer_map!(self, "shr", value => value.checked_shr(amount))
+    }
+    /// Rotations wrap the amount around the bit width.
+    pub fn rotate_left(self, amount: u32) -> Result<Number, Error> {
+        integer_map!(self, "rotl", value => Some(value.rotate_left(amount)))
+    }
+    pub fn rotate_right(self, amount: u32) -> Result<Number, Error> {
+        integer_map!(self, "rotr", value => Some(value.rotate_right(amount)))
+    }
+    pub fn popcount(self) -> Result<Number, Error> {
+        integer_count!(self, count_ones)
+    }
+    pub fn leading_zeros(self) -> Result<Number, Error> {
+        integer_count!(self, leading_zeros)
+    }
+    pub fn trailing_zeros(self) -> Result<Number, Error> {
+        integer_count!(self, trailing_zeros)
+    }
+}
+
+impl std::ops::Add for Number {
+    type Output = Result<Number, Error>;
+    fn add(self, other: Number) -> Result<Number, Error> {
+        checked_op!(self, other, checked_add, +)
+    }
 }
 impl std::ops::Sub for Number {
-    type Output = Result<Number, String>;
-    fn sub(self, other: Number) -> Result<Number, String> {
-        match (self, other) {
-            (Number::I8(v1), Number::I8(v2)) => Ok(Number::I8(v1-v2)),
-            (Number::I16(v1), Number::I16(v2)) => Ok(Number::I16(v1-v2)),
-            (Number::I32(v1), Number::I32(v2)) => Ok(Number::I32(v1-v2)),
-            (Number::I64(v1), Number::I64(v2)) => Ok(Number::I64(v1-v2)),
-            (Number::I128(v1), Number::I128(v2)) => Ok(Number::I128(v1-v2)),
-            (Number::U8(v1), Number::U8(v2)) => Ok(Number::U8(v1-v2)),
-            (Number::U16(v1), Number::U16(v2)) => Ok(Number::U16(v1-v2)),
-            (Number::U32(v1), Number::U32(v2)) => Ok(Number::U32(v1-v2)),
-            (Number::U64(v1), Number::U64(v2)) => Ok(Number::U64(v1-v2)),
-            (Number::U128(v1), Number::U128(v2)) => Ok(Number::U128(v1-v2)),
-            (Number::F32(v1), Number::F32(v2)) => Ok(Number::F32(v1-v2)),
-            (Number::F64(v1), Number::F64(v2)) => Ok(Number::F64(v1-v2)),
-            _ => Err("Mismatched number types for '-'".to_string())
-        }
+    type Output = Result<Number, Error>;
+    fn sub(self, other: Number) -> Result<Number, Error> {
+        checked_op!(self, other, checked_sub, -)
     }
 }
 impl std::ops::Mul for Number {
-    type Output = Result<Number, String>;
-    fn mul(self, other: Number) -> Result<Number, String> {
-        match (self, other) {
-            (Number::I8(v1), Number::I8(v2)) => Ok(Number::I8(v1*v2)),
-            (Number::I16(v1), Number::I16(v2)) => Ok(Number::I16(v1*v2)),
-            (Number::I32(v1), Number::I32(v2)) => Ok(Number::I32(v1*v2)),
-            (Number::I64(v1), Number::I64(v2)) => Ok(Number::I64(v1*v2)),
-            (Number::I128(v1), Number::I128(v2)) => Ok(Number::I128(v1*v2)),
-            (Number::U8(v1), Number::U8(v2)) => Ok(Number::U8(v1*v2)),
-            (Number::U16(v1), Number::U16(v2)) => Ok(Number::U16(v1*v2)),
-            (Number::U32(v1), Number::U32(v2)) => Ok(Number::U32(v1*v2)),
-            (Number::U64(v1), Number::U64(v2)) => Ok(Number::U64(v1*v2)),
-            (Number::U128(v1), Number::U128(v2)) => Ok(Number::U128(v1*v2)),
-            (Number::F32(v1), Number::F32(v2)) => Ok(Number::F32(v1*v2)),
-            (Number::F64(v1), Number::F64(v2)) => Ok(Number::F64(v1*v2)),
-            _ => Err("Mismatched number types for '*'".to_string())
-        }
+    type Output = Result<Number, Error>;
+    fn mul(self, other: Number) -> Result<Number, Error> {
+        checked_op!(self, other, checked_mul, *)
     }
 }
 impl std::ops::Div for Number {
-    type Output = Result<Number, String>;
-    fn div(self, other: Number) -> Result<Number, String> {
-        match (self, other) {
-            (Number::I8(v1), Number::I8(v2)) => Ok(Number::I8(v1/v2)),
-            (Number::I16(v1), Number::I16(v2)) => Ok(Number::I16(v1/v2)),
-            (Number::I32(v1), Number::I32(v2)) => Ok(Number::I32(v1/v2)),
-            (Number::I64(v1), Number::I64(v2)) => Ok(Number::I64(v1/v2)),
-            (Number::I128(v1), Number::I128(v2)) => Ok(Number::I128(v1/v2)),
-            (Number::U8(v1), Number::U8(v2)) => Ok(Number::U8(v1/v2)),
-            (Number::U16(v1), Number::U16(v2)) => Ok(Number::U16(v1/v2)),
-            (Number::U32(v1), Number::U32(v2)) => Ok(Number::U32(v1/v2)),
-            (Number::U64(v1), Number::U64(v2)) => Ok(Number::U64(v1/v2)),
-            (Number::U128(v1), Number::U128(v2)) => Ok(Number::U128(v1/v2)),
-            (Number::F32(v1), Number::F32(v2)) => Ok(Number::F32(v1/v2)),
-            (Number::F64(v1), Number::F64(v2)) => Ok(Number::F64(v1/v2)),
-            _ => Err("Mismatched number types for '/'".to_string())
-        }
+    type Output = Result<Number, Error>;
+    fn div(self, other: Number) -> Result<Number, Error> {
+        checked_division!(self, other, checked_div, /)
     }
 }
 impl std::ops::Rem for Number {
-    type Output = Result<Number, String>;
-    fn rem(self, other: Number) -> Result<Number, String> {
+    type Output = Result<Number, Error>;
+    fn rem(self, other: Number) -> Result<Number, Error> {
+        checked_division!(self, other, checked_rem, %)
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Number) -> bool {
+        self.partial_cmp(other) == Some(std::cmp::Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Number) -> Option<std::cmp::Ordering> {
+        if !self.same_type(other) {
+            let (v1, v2) = self.clone().promote(other.clone()).ok()?;
+            return v1.partial_cmp(&v2);
+        }
         match (self, other) {
-            (Number::I8(v1), Number::I8(v2)) => Ok(Number::I8(v1%v2)),
-            (Number::I16(v1), Number::I16(v2)) => Ok(Number::I16(v1%v2)),
-            (Number::I32(v1), Number::I32(v2)) => Ok(Number::I32(v1%v2)),
-            (Number::I64(v1), Number::I64(v2)) => Ok(Number::I64(v1%v2)),
-            (Number::I128(v1), Number::I128(v2)) => Ok(Number::I128(v1%v2)),
-            (Number::U8(v1), Number::U8(v2)) => Ok(Number::U8(v1%v2)),
-            (Number::U16(v1), Number::U16(v2)) => Ok(Number::U16(v1%v2)),
-            (Number::U32(v1), Number::U32(v2)) => Ok(Number::U32(v1%v2)),
-            (Number::U64(v1), Number::U64(v2)) => Ok(Number::U64(v1%v2)),
-            (Number::U128(v1), Number::U128(v2)) => Ok(Number::U128(v1%v2)),
-            (Number::F32(v1), Number::F32(v2)) => Ok(Number::F32(v1%v2)),
-            (Number::F64(v1), Number::F64(v2)) => Ok(Number::F64(v1%v2)),
-            _ => Err("Mismatched number types for '%'".to_string())
+            (Number::I8(v1), Number::I8(v2)) => v1.partial_cmp(v2),
+            (Number::I16(v1), Number::I16(v2)) => v1.partial_cmp(v2),
+            (Number::I32(v1), Number::I32(v2)) => v1.partial_cmp(v2),
+            (Number::I64(v1), Number::I64(v2)) => v1.partial_cmp(v2),
+            (Number::I128(v1), Number::I128(v2)) => v1.partial_cmp(v2),
+            (Number::U8(v1), Number::U8(v2)) => v1.partial_cmp(v2),
+            (Number::U16(v1), Number::U16(v2)) => v1.partial_cmp(v2),
+            (Number::U32(v1), Number::U32(v2)) => v1.partial_cmp(v2),
+            (Number::U64(v1), Number::U64(v2)) => v1.partial_cmp(v2),
+            (Number::U128(v1), Number::U128(v2)) => v1.partial_cmp(v2),
+            (Number::F32(v1), Number::F32(v2)) => v1.partial_cmp(v2),
+            (Number::F64(v1), Number::F64(v2)) => v1.partial_cmp(v2),
+            (Number::BigInt(v1), Number::BigInt(v2)) => v1.partial_cmp(v2),
+            (Number::Rational(v1), Number::Rational(v2)) => v1.partial_cmp(v2),
+            (Number::Decimal(v1), Number::Decimal(v2)) => v1.partial_cmp(v2),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::I8(value) => value.fmt(f),
+            Number::I16(value) => value.fmt(f),
+            Number::I32(value) => value.fmt(f),
+            Number::I64(value) => value.fmt(f),
+            Number::I128(value) => value.fmt(f),
+            Number::U8(value) => value.fmt(f),
+            Number::U16(value) => value.fmt(f),
+            Number::U32(value) => value.fmt(f),
+            Number::U64(value) => value.fmt(f),
+            Number::U128(value) => value.fmt(f),
+            Number::F32(value) => value.fmt(f),
+            Number::F64(value) => value.fmt(f),
+            Number::BigInt(value) => value.fmt(f),
+            Number::Rational(value) => value.fmt(f),
+            Number::Decimal(value) => value.fmt(f),
         }
     }
 }
@@ -1,44 +1,101 @@
-use crate::lexer::Token;
+use crate::{
+    error::Error,
+    lexer::Token,
+    span::Span
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Node {
-    Tok(Token),
-    Group(Vec<Node>)
+    Tok(Token, Span),
+    Group(Vec<Node>, Span),
+    Map(Vec<Node>, Span),
+    List(Vec<Node>, Span)
 }
 
-pub fn parse(tokens: Vec<Token>) -> Result<Vec<Node>, String> {
+fn symbol(token: &Token) -> &'static str {
+    match token {
+        Token::OpenBracket => "{",
+        Token::OpenMap => "#{",
+        Token::OpenList => "[",
+        Token::CloseList => "]",
+        _ => "}"
+    }
+}
+
+fn closer(opener: &Token) -> Token {
+    match opener {
+        Token::OpenList => Token::CloseList,
+        _ => Token::CloseBracket
+    }
+}
+
+/// A bracket that has been opened but not yet closed.
+struct Frame {
+    opener: Token,
+    span: Span,
+    nodes: Vec<Node>
+}
+
+impl Frame {
+    fn into_node(self) -> Node {
+        match self.opener {
+            Token::OpenMap => Node::Map(self.nodes, self.span),
+            Token::OpenList => Node::List(self.nodes, self.span),
+            _ => Node::Group(self.nodes, self.span)
+        }
+    }
+}
+
+fn parse_error(message: String, span: Span) -> Error {
+    Error::ParseError { message, span }
+}
+
+/// Builds the tree, carrying on past bracket errors so that every one of
+/// them is reported, in source order.
+pub fn parse(tokens: Vec<(Token, Span)>) -> Result<Vec<Node>, Error> {
 
     let mut result = vec![];
-    let mut working = vec![];
-    let mut level = 0;
+    let mut open: Vec<Frame> = vec![];
+    let mut errors = vec![];
 
-    for token in tokens {
+    for (token, span) in tokens {
         match token {
-            Token::OpenBracket => {
-                level += 1;
-                if level > 1 {
-                    working.push(token);
-                }
+            Token::OpenBracket | Token::OpenMap | Token::OpenList => {
+                open.push(Frame { opener: token, span, nodes: vec![] });
             },
-            Token::CloseBracket => {
-                level -= 1;
-                if level == 0 {
-                    let inner = parse(working)?;
-                    result.push(Node::Group(inner));
-                    working = vec![];
-                } else {
-                    working.push(token);
+            Token::CloseBracket | Token::CloseList => {
+                let Some(frame) = open.pop() else {
+                    errors.push(parse_error(format!("unexpected '{}'", symbol(&token)), span));
+                    continue;
+                };
+                let expected = closer(&frame.opener);
+                if token != expected {
+                    errors.push(parse_error(
+                        format!("expected '{}' to close '{}', found '{}'", symbol(&expected), symbol(&frame.opener), symbol(&token)),
+                        span
+                    ));
                 }
-            },
-            _ => {
-                if level == 0 {
-                    result.push(Node::Tok(token));
-                } else {
-                    working.push(token);
+                let node = frame.into_node();
+                match open.last_mut() {
+                    Some(parent) => parent.nodes.push(node),
+                    None => result.push(node)
                 }
+            },
+            _ => match open.last_mut() {
+                Some(frame) => frame.nodes.push(Node::Tok(token, span)),
+                None => result.push(Node::Tok(token, span))
             }
         }
     }
 
-    Ok(result)
+    for frame in open {
+        errors.push(parse_error(format!("unclosed '{}'", symbol(&frame.opener)), frame.span));
+    }
+    errors.sort_by_key(|error| error.span().map(|span| (span.line, span.column)));
+
+    match errors.len() {
+        0 => Ok(result),
+        1 => Err(errors.remove(0)),
+        _ => Err(Error::Multiple(errors))
+    }
 }
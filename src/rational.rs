@@ -0,0 +1,183 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul, Neg, Sub}
+};
+
+use crate::{bigint::BigInt, decimal::RoundingMode};
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Rational {
+    parts: Box<(BigInt, BigInt)>
+}
+
+impl Rational {
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Option<Rational> {
+        if denominator.is_zero() {
+            return None;
+        }
+        let (numerator, denominator) = if denominator.is_negative() {
+            (-&numerator, -&denominator)
+        } else {
+            (numerator, denominator)
+        };
+        let divisor = numerator.gcd(&denominator);
+        if divisor.is_zero() {
+            return Some(Rational::from_integer(BigInt::zero()));
+        }
+        Some(Rational {
+            parts: Box::new((numerator.checked_div(&divisor)?, denominator.checked_div(&divisor)?))
+        })
+    }
+
+    pub fn from_integer(value: BigInt) -> Rational {
+        Rational { parts: Box::new((value, BigInt::from(1u128))) }
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.parts.0
+    }
+
+    pub fn denominator(&self) -> &BigInt {
+        &self.parts.1
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator().is_zero()
+    }
+
+    pub fn abs(&self) -> Rational {
+        Rational { parts: Box::new((self.numerator().abs(), self.denominator().clone())) }
+    }
+
+    pub fn pow(&self, exponent: u32) -> Rational {
+        Rational { parts: Box::new((self.numerator().pow(exponent), self.denominator().pow(exponent))) }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator() == &BigInt::from(1u128)
+    }
+
+    pub fn trunc(&self) -> BigInt {
+        self.numerator().checked_div(self.denominator()).unwrap_or_default()
+    }
+
+    /// This value times `10^places`, rounded to an integer with `mode`, and
+    /// whether that was exact.
+    pub fn round_to(&self, places: u32, mode: RoundingMode) -> (BigInt, bool) {
+        let numerator = self.numerator() * &BigInt::from(10u128).pow(places);
+        let (quotient, remainder) = numerator.div_rem(self.denominator()).unwrap_or_default();
+        let half = (&remainder.abs() * &BigInt::from(2u128)).cmp(self.denominator());
+        let exact = remainder.is_zero();
+        if mode.away_from_zero(numerator.is_negative(), exact, half, quotient.is_odd()) {
+            let step = BigInt::from(if numerator.is_negative() { -1i128 } else { 1 });
+            (&quotient + &step, false)
+        } else {
+            (quotient, exact)
+        }
+    }
+
+    pub fn checked_div(&self, other: &Rational) -> Option<Rational> {
+        Rational::new(
+            self.numerator() * other.denominator(),
+            self.denominator() * other.numerator()
+        )
+    }
+
+    pub fn checked_rem(&self, other: &Rational) -> Option<Rational> {
+        let quotient = Rational::from_integer(self.checked_div(other)?.trunc());
+        Some(self - &(other * &quotient))
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator().to_f64() / self.denominator().to_f64()
+    }
+
+    pub fn from_f64(value: f64) -> Option<Rational> {
+        if !value.is_finite() {
+            return None;
+        }
+        let bits = value.abs().to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = if biased == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased - 1075)
+        };
+        let mantissa = BigInt::from(mantissa as u128);
+        let mantissa = if value < 0.0 { -&mantissa } else { mantissa };
+        if exponent >= 0 {
+            Some(Rational::from_integer(mantissa.shl(exponent as usize)))
+        } else {
+            Rational::new(mantissa, BigInt::from(1u128).shl(-exponent as usize))
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Rational> {
+        match text.split_once('/') {
+            Some((numerator, denominator)) => Rational::new(BigInt::parse(numerator)?, BigInt::parse(denominator)?),
+            None => Some(Rational::from_integer(BigInt::parse(text)?))
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.numerator() * other.denominator()).cmp(&(other.numerator() * self.denominator()))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational { parts: Box::new((-self.numerator(), self.denominator().clone())) }
+    }
+}
+
+impl Add for &Rational {
+    type Output = Rational;
+    fn add(self, other: &Rational) -> Rational {
+        let numerator = &(self.numerator() * other.denominator()) + &(other.numerator() * self.denominator());
+        let denominator = self.denominator() * other.denominator();
+        Rational::new(numerator, denominator).unwrap_or_else(|| Rational::from_integer(BigInt::zero()))
+    }
+}
+
+impl Sub for &Rational {
+    type Output = Rational;
+    fn sub(self, other: &Rational) -> Rational {
+        self + &-other
+    }
+}
+
+impl Mul for &Rational {
+    type Output = Rational;
+    fn mul(self, other: &Rational) -> Rational {
+        let numerator = self.numerator() * other.numerator();
+        let denominator = self.denominator() * other.denominator();
+        Rational::new(numerator, denominator).unwrap_or_else(|| Rational::from_integer(BigInt::zero()))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator())
+        } else {
+            write!(f, "{}/{}", self.numerator(), self.denominator())
+        }
+    }
+}
+
+impl fmt::Debug for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    env,
+    fs::OpenOptions,
+    io::{self, BufRead, Write},
+    path::PathBuf
+};
+use conc::{lexer::{self, Token}, span::Span, Error, Interpreter};
+
+use crate::report;
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".conc_history"))
+}
+
+fn save_history(entry: &str) {
+    let Some(path) = history_path() else { return };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{}", entry);
+    }
+}
+
+fn bracket_depth(tokens: &[(Token, Span)]) -> i64 {
+    tokens.iter().fold(0, |depth, (token, _)| match token {
+        Token::OpenBracket | Token::OpenMap | Token::OpenList => depth + 1,
+        Token::CloseBracket | Token::CloseList => depth - 1,
+        _ => depth
+    })
+}
+
+pub fn run() {
+    let mut interpreter = Interpreter::new();
+    let mut buffer = String::new();
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        print!("{}", if buffer.is_empty() { "> " } else { ". " });
+        let _ = io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            break;
+        };
+        buffer.push_str(&line);
+        buffer.push('\n');
+
+        let tokens = match lexer::lex(buffer.clone(), "<repl>") {
+            Ok(tokens) => tokens,
+            Err(Error::LexError { unclosed: true, .. }) => continue,
+            Err(error) => {
+                report(&error);
+                buffer.clear();
+                continue;
+            }
+        };
+        if bracket_depth(&tokens) > 0 {
+            continue;
+        }
+
+        let entry = std::mem::take(&mut buffer);
+        save_history(entry.trim_end());
+        match interpreter.eval_named(&entry, "<repl>") {
+            Ok(()) => println!("{:?}", interpreter.stack()),
+            Err(error) => report(&error)
+        }
+    }
+}
@@ -1,33 +1,85 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, rc::Rc};
 
 use crate::{
-    parser::Node,
-    lexer::Token,
-    value::Value,
-    number::Number,
+    bytecode::{compile, Builtin, Code, Interner, Op},
+    parser::{parse, Node},
+    lexer::lex,
+    value::{number_operands, Closure, Value},
+    number::{CastMode, Number},
+    decimal::RoundingMode,
+    math,
+    text,
+    format,
+    map::{self, Map},
+    vector,
+    scope::Scope,
+    error::Error,
 };
 
-const BUILTINS: &[&str] = &[
-    "+", "-", "*", "/",
-    "true", "false", "==", "!=", ">", "<", ">=", "<=",
-    "print", "debug",
-    "swap", "take",
-    "global_bind",
-    "cast",
-    "if"
-];
-
-struct ScopedValue {
-    value: Value,
-    scopes: usize
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StackEffect {
+    pub inputs: usize,
+    pub outputs: usize
 }
 
-struct State {
-    stack: Vec<Value>,
-    global: HashMap<String, Value>,
-    scoped: HashMap<String, ScopedValue>
+pub type NativeFunction = dyn Fn(Vec<Value>) -> Result<Vec<Value>, Error>;
+
+#[derive(Clone)]
+pub(crate) struct NativeWord {
+    pub(crate) effect: StackEffect,
+    pub(crate) function: Rc<NativeFunction>
+}
+
+#[derive(Clone)]
+pub(crate) struct State {
+    pub(crate) stack: Vec<Value>,
+    pub(crate) words: Interner,
+    pub(crate) global: Vec<Option<Value>>,
+    pub(crate) native: Vec<Option<NativeWord>>,
+    pub(crate) strict_numbers: bool,
+    pub(crate) rounding: RoundingMode,
+    root: Rc<Scope>,
+    scope: Rc<Scope>
+}
+
+impl Default for State {
+    fn default() -> State {
+        let root = Rc::new(Scope::default());
+        State {
+            stack: vec![],
+            words: Interner::default(),
+            global: vec![],
+            native: vec![],
+            strict_numbers: false,
+            rounding: RoundingMode::default(),
+            root: root.clone(),
+            scope: root
+        }
+    }
+}
+
+pub(crate) fn slot<T>(slots: &mut Vec<Option<T>>, id: usize) -> &mut Option<T> {
+    if slots.len() <= id {
+        slots.resize_with(id + 1, || None);
+    }
+    &mut slots[id]
+}
+
+/// A builtin always takes precedence when a word is called, so binding,
+/// setting or registering a name that one already has is an error.
+pub(crate) fn check_bindable(name: &str) -> Result<(), Error> {
+    match Builtin::from_name(name) {
+        Some(_) => Err(Error::WordAlreadyDefined(name.to_string())),
+        None => Ok(())
+    }
 }
 
+/// The position `index` places below the last of `length` items.
+fn from_end(index: usize, length: usize) -> Result<usize, Error> {
+    length.checked_sub(index)
+        .and_then(|position| position.checked_sub(1))
+        .ok_or(Error::IndexOutOfRange { index, length })
+}
 
 macro_rules! check_types {
     ($($value:expr, $type:ident),*) => {
@@ -39,137 +91,467 @@ macro_rules! check_types {
 
 macro_rules! check_type {
     ($value:expr, $type:ident) => {
-        if let Value::$type(result) = $value {
-            Ok(result)
-        } else {
-            Err("Wrong type".to_string())
+        match $value {
+            Value::$type(result) => Ok(result),
+            value => Err(Error::TypeMismatch { expected: stringify!($type), found: value.type_name() })
         }
     }
 }
 
 impl State {
 
-    fn pop(&mut self) -> Result<Value, String> {
+    pub(crate) fn compile(&mut self, tree: &[Node]) -> Result<Code, Error> {
+        compile(tree, &mut self.words)
+    }
+
+    pub(crate) fn run(&mut self, code: &Code) -> Result<(), Error> {
+        for (op, span) in code.ops.iter().zip(&code.spans) {
+            self.execute_op(op).map_err(|error| error.at(span))?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn pop(&mut self) -> Result<Value, Error> {
         self.stack
             .pop()
-            .ok_or_else(|| "Stack empty when popped".to_string())
+            .ok_or(Error::StackUnderflow)
     }
 
-    fn binary_op(&mut self, function: fn(Value, Value) -> Result<Value, String>) -> Result<(), String> {
-        let first = self.pop()?;
-        let second = self.pop()?;
-        let result = function(second, first)?;
-        self.stack.push(result);
+    pub(crate) fn pop_number(&mut self) -> Result<Number, Error> {
+        Ok(check_types!(self.pop()?, Number))
+    }
+
+    pub(crate) fn pop_string(&mut self) -> Result<String, Error> {
+        Ok(check_types!(self.pop()?, String))
+    }
+
+    pub(crate) fn pop_vector(&mut self) -> Result<Rc<Vec<Value>>, Error> {
+        Ok(check_types!(self.pop()?, Vector))
+    }
+
+    pub(crate) fn pop_map(&mut self) -> Result<Rc<Map>, Error> {
+        Ok(check_types!(self.pop()?, Map))
+    }
+
+    pub(crate) fn pop_function(&mut self) -> Result<Closure, Error> {
+        Ok(check_types!(self.pop()?, Function))
+    }
+
+    pub(crate) fn pop_index(&mut self) -> Result<usize, Error> {
+        match check_types!(self.pop()?, Number) {
+            Number::U64(index) => Ok(index as usize),
+            number => Err(Error::TypeMismatch { expected: "u64", found: number.type_name() })
+        }
+    }
+
+    fn check_strict(&self, first: &Number, second: &Number) -> Result<(), Error> {
+        if self.strict_numbers && !first.same_type(second) {
+            return Err(Error::TypeMismatch { expected: first.type_name(), found: second.type_name() });
+        }
         Ok(())
     }
 
-    fn logic_op(&mut self, function: fn(Value, Value) -> Value) -> Result<(), String> {
+    /// Takes two numbers of the same type off the top of the stack. This is
+    /// the common case, and skipping the checks and promotion `pop_numbers`
+    /// callers go through makes arithmetic several times faster.
+    fn pop_same_type(&mut self) -> Option<(Number, Number)> {
+        match &self.stack[..] {
+            [.., Value::Number(second), Value::Number(first)] if second.same_type(first) => (),
+            _ => return None
+        }
+        match (self.stack.pop()?, self.stack.pop()?) {
+            (Value::Number(first), Value::Number(second)) => Some((second, first)),
+            _ => None
+        }
+    }
+
+    fn pop_numbers(&mut self) -> Result<(Number, Number), Error> {
         let first = self.pop()?;
         let second = self.pop()?;
-        let result = function(second, first);
-        self.stack.push(result);
+        let (second, first) = number_operands(second, first)?;
+        self.check_strict(&second, &first)?;
+        Ok((second, first))
+    }
+
+    fn number_op(&mut self, function: fn(Number, Number) -> Result<Number, Error>) -> Result<(), Error> {
+        if let Some((second, first)) = self.pop_same_type() {
+            self.stack.push(Value::Number(function(second, first)?));
+            return Ok(());
+        }
+        let (second, first) = self.pop_numbers()?;
+        self.stack.push(Value::Number(function(second, first)?));
+        Ok(())
+    }
+
+    fn overflowing_op(&mut self, function: fn(Number, Number) -> Result<(Number, bool), Error>) -> Result<(), Error> {
+        let (second, first) = self.pop_numbers()?;
+        let (result, overflowed) = function(second, first)?;
+        self.stack.push(Value::Number(result));
+        self.stack.push(Value::Boolean(overflowed));
         Ok(())
     }
 
-    fn execute_function(&mut self, function: &Vec<Node>) -> Result<(), String> {
-        for node in function {
-            self.execute_node(node)?;
+    fn logic_op(&mut self, boolean: fn(bool, bool) -> bool, number: fn(Number, Number) -> Result<Number, Error>) -> Result<(), Error> {
+        if let [.., Value::Boolean(second), Value::Boolean(first)] = self.stack[..] {
+            self.stack.truncate(self.stack.len() - 2);
+            self.stack.push(Value::Boolean(boolean(second, first)));
+            return Ok(());
+        }
+        self.number_op(number)
+    }
+
+    fn pop_u32(&mut self) -> Result<u32, Error> {
+        match check_types!(self.pop()?, Number).cast_to("u32", CastMode::Checked)? {
+            Number::U32(value) => Ok(value),
+            number => Err(Error::TypeMismatch { expected: "u32", found: number.type_name() })
         }
+    }
+
+    fn pop_binding_name(&mut self) -> Result<String, Error> {
+        let name = check_types!(self.pop()?, String);
+        check_bindable(&name)?;
+        Ok(name)
+    }
+
+    fn shift_op(&mut self, function: fn(Number, Number) -> Result<Number, Error>) -> Result<(), Error> {
+        let amount = check_types!(self.pop()?, Number);
+        let value = check_types!(self.pop()?, Number);
+        self.stack.push(Value::Number(function(value, amount)?));
+        Ok(())
+    }
+
+    fn rotate_op(&mut self, function: fn(Number, u32) -> Result<Number, Error>) -> Result<(), Error> {
+        let amount = self.pop_u32()?;
+        let value = check_types!(self.pop()?, Number);
+        self.stack.push(Value::Number(function(value, amount)?));
+        Ok(())
+    }
+
+    fn unary_op(&mut self, function: fn(Number) -> Result<Number, Error>) -> Result<(), Error> {
+        let value = check_types!(self.pop()?, Number);
+        self.stack.push(Value::Number(function(value)?));
+        Ok(())
+    }
+
+    /// Numbers are promoted to a common type first; any other pair of values
+    /// compares as `Value::partial_cmp` orders them, and is false when it
+    /// doesn't.
+    fn compare_op(&mut self, test: fn(Ordering) -> bool) -> Result<(), Error> {
+        let ordering = if let Some((second, first)) = self.pop_same_type() {
+            second.partial_cmp(&first)
+        } else if let [.., Value::Number(_), Value::Number(_)] = self.stack[..] {
+            let (second, first) = self.pop_numbers()?;
+            let (second, first) = second.promote(first)?;
+            second.partial_cmp(&first)
+        } else {
+            let first = self.pop()?;
+            let second = self.pop()?;
+            second.partial_cmp(&first)
+        };
+        self.stack.push(Value::Boolean(ordering.is_some_and(test)));
         Ok(())
     }
 
-    fn execute_builtin(&mut self, key: &String) -> Result<(), String> {
-        match key.as_str() {
-            "+" => self.binary_op(|a,b| a+b)?,
-            "-" => self.binary_op(|a,b| a-b)?,
-            "*" => self.binary_op(|a,b| a*b)?,
-            "/" => self.binary_op(|a,b| a/b)?,
-            ">" => self.binary_op(|a,b| Ok(Value::Boolean(a>b)))?,
-            "<" => self.binary_op(|a,b| Ok(Value::Boolean(a<b)))?,
-            ">=" => self.binary_op(|a,b| Ok(Value::Boolean(a>=b)))?,
-            "<=" => self.binary_op(|a,b| Ok(Value::Boolean(a<=b)))?,
-            "==" => self.logic_op(|a,b| Value::Boolean(a==b))?,
-            "!=" => self.logic_op(|a,b| Value::Boolean(a!=b))?,
-            "true" => self.stack.push(Value::Boolean(true)),
-            "false" => self.stack.push(Value::Boolean(false)),
-            "debug" => println!("{:?}", self.stack),
-            "print" => {
+    fn equals(&self, first: &Value, second: &Value) -> bool {
+        match (first, second) {
+            (Value::Number(v1), Value::Number(v2)) if self.strict_numbers => {
+                v1.type_name() == v2.type_name() && v1 == v2
+            },
+            _ => first == second
+        }
+    }
+
+    fn run_in(&mut self, scope: Rc<Scope>, code: &Code) -> Result<(), Error> {
+        let previous = std::mem::replace(&mut self.scope, scope);
+        let result = self.run(code);
+        self.scope = previous;
+        result
+    }
+
+    /// Runs `code` in a fresh frame below `parent`, releasing the frame
+    /// afterwards unless something outside it still refers to it.
+    fn run_frame(&mut self, parent: &Rc<Scope>, code: &Code) -> Result<(), Error> {
+        let frame = Scope::child(parent);
+        let result = self.run_in(frame.clone(), code);
+        frame.release();
+        result
+    }
+
+    pub(crate) fn call(&mut self, function: &Closure) -> Result<(), Error> {
+        self.run_frame(&function.scope, &function.code)
+    }
+
+    /// A copy whose top-level variables are independent of this one's.
+    pub(crate) fn fork(&self) -> State {
+        let root = self.root.copy();
+        State { root: root.clone(), scope: root, ..self.clone() }
+    }
+
+    pub(crate) fn run_block(&mut self, function: &Closure) -> Result<(), Error> {
+        self.run_in(function.scope.clone(), &function.code)
+    }
+
+    fn execute_builtin(&mut self, builtin: Builtin) -> Result<(), Error> {
+        match builtin {
+            Builtin::Add => self.number_op(|a,b| a+b)?,
+            Builtin::Sub => self.number_op(|a,b| a-b)?,
+            Builtin::Mul => self.number_op(|a,b| a*b)?,
+            Builtin::Div => {
+                let (second, first) = self.pop_numbers()?;
+                self.stack.push(Value::Number(second.divide(first, self.rounding)?));
+            },
+            Builtin::Rem => self.number_op(|a,b| a%b)?,
+            Builtin::WrappingAdd => self.number_op(Number::wrapping_add)?,
+            Builtin::WrappingSub => self.number_op(Number::wrapping_sub)?,
+            Builtin::WrappingMul => self.number_op(Number::wrapping_mul)?,
+            Builtin::SaturatingAdd => self.number_op(Number::saturating_add)?,
+            Builtin::SaturatingSub => self.number_op(Number::saturating_sub)?,
+            Builtin::SaturatingMul => self.number_op(Number::saturating_mul)?,
+            Builtin::OverflowingAdd => self.overflowing_op(Number::overflowing_add)?,
+            Builtin::OverflowingSub => self.overflowing_op(Number::overflowing_sub)?,
+            Builtin::OverflowingMul => self.overflowing_op(Number::overflowing_mul)?,
+            Builtin::And => self.logic_op(|a, b| a && b, Number::bit_and)?,
+            Builtin::Or => self.logic_op(|a, b| a || b, Number::bit_or)?,
+            Builtin::Xor => self.logic_op(|a, b| a != b, Number::bit_xor)?,
+            Builtin::Not => match self.pop()? {
+                Value::Boolean(value) => self.stack.push(Value::Boolean(!value)),
+                value => {
+                    self.stack.push(value);
+                    self.unary_op(Number::bit_not)?;
+                }
+            },
+            Builtin::Shl => self.shift_op(Number::shift_left)?,
+            Builtin::Shr => self.shift_op(Number::shift_right)?,
+            Builtin::Rotl => self.rotate_op(Number::rotate_left)?,
+            Builtin::Rotr => self.rotate_op(Number::rotate_right)?,
+            Builtin::PopCount => self.unary_op(Number::popcount)?,
+            Builtin::LeadingZeros => self.unary_op(Number::leading_zeros)?,
+            Builtin::TrailingZeros => self.unary_op(Number::trailing_zeros)?,
+            Builtin::Gt => self.compare_op(Ordering::is_gt)?,
+            Builtin::Lt => self.compare_op(Ordering::is_lt)?,
+            Builtin::Ge => self.compare_op(Ordering::is_ge)?,
+            Builtin::Le => self.compare_op(Ordering::is_le)?,
+            Builtin::Eq | Builtin::Ne => {
+                let first = self.pop()?;
+                let second = self.pop()?;
+                let equal = self.equals(&second, &first);
+                self.stack.push(Value::Boolean(equal == (builtin == Builtin::Eq)));
+            },
+            Builtin::StrictNumbers => {
+                self.strict_numbers = check_types!(self.pop()?, Boolean);
+            },
+            Builtin::RoundingMode => {
+                let name = check_types!(self.pop()?, String);
+                self.rounding = RoundingMode::from_name(&name).ok_or(Error::UnknownRoundingMode(name))?;
+            },
+            Builtin::Round => {
+                let places = self.pop_u32()?;
+                let value = check_types!(self.pop()?, Number);
+                self.stack.push(Value::Number(value.round(places, self.rounding)?));
+            },
+            Builtin::True => self.stack.push(Value::Boolean(true)),
+            Builtin::False => self.stack.push(Value::Boolean(false)),
+            Builtin::Debug => println!("{:?}", self.stack),
+            Builtin::Print => {
                 let item = self.pop()?;
-                println!("{:?}", item);
+                println!("{}", item);
             },
-            "swap" => {
+            Builtin::Format => format::format(self)?,
+            Builtin::Swap => {
                 let item1 = self.pop()?;
                 let item2 = self.pop()?;
                 self.stack.push(item1);
                 self.stack.push(item2);
             },
-            "take" => {
-                let index = check_types!(self.pop()?, Number);
-                if let Number::U64(index) = index {
-                    let item = self.stack.remove(self.stack.len()-1-(index as usize));
-                    self.stack.push(item);
-                }
+            Builtin::Take => {
+                let index = self.pop_index()?;
+                let position = from_end(index, self.stack.len())?;
+                let item = self.stack.remove(position);
+                self.stack.push(item);
             },
-            "if" => {
+            Builtin::Clone => {
+                let item = self.pop()?;
+                self.stack.push(item.clone());
+                self.stack.push(item);
+            },
+            Builtin::Delete => {
+                self.pop()?;
+            },
+            Builtin::If => {
                 let function = self.pop()?;
                 let condition = self.pop()?;
                 let (function, condition) = check_types!(function, Function, condition, Boolean);
                 if condition {
-                    self.execute_function(&function)?;
+                    self.run_block(&function)?;
                 }
             },
-            "global_bind" => {
+            Builtin::Repeat => {
+                let function = check_types!(self.pop()?, Function);
+                let count = self.pop_index()?;
+                for _ in 0..count {
+                    self.run_block(&function)?;
+                }
+            },
+            Builtin::For => {
+                let function = self.pop()?;
+                let vector = self.pop()?;
+                let (function, vector) = check_types!(function, Function, vector, Vector);
+                for item in Rc::unwrap_or_clone(vector) {
+                    self.stack.push(item);
+                    self.run_block(&function)?;
+                }
+            },
+            Builtin::Call => {
+                let function = check_types!(self.pop()?, Function);
+                self.call(&function)?;
+            },
+            Builtin::GlobalBind => {
+                let name = self.pop_binding_name()?;
+                let item = self.pop()?;
+                let id = self.words.intern(&name);
+                *slot(&mut self.global, id) = Some(item);
+            },
+            Builtin::GlobalGet => {
                 let name = check_types!(self.pop()?, String);
+                let item = self.words.get(&name)
+                    .and_then(|id| self.global_get(id))
+                    .ok_or(Error::UnknownVariable(name))?;
+                self.stack.push(item);
+            },
+            Builtin::ScopedBind => {
+                let name = self.pop_binding_name()?;
+                let value = self.pop()?;
+                let id = self.words.intern(&name);
+                self.scope.bind(id, value);
+            },
+            Builtin::ScopedGet => {
+                let name = check_types!(self.pop()?, String);
+                let item = self.words.get(&name)
+                    .and_then(|id| self.scoped_get(id))
+                    .ok_or(Error::UnknownVariable(name))?;
+                self.stack.push(item);
+            },
+            Builtin::Insert => {
+                let index = self.pop_index()?;
                 let item = self.pop()?;
-                self.global.insert(name, item);
+                let mut vector = check_types!(self.pop()?, Vector);
+                let position = vector.len()
+                    .checked_sub(index)
+                    .ok_or(Error::IndexOutOfRange { index, length: vector.len() })?;
+                Rc::make_mut(&mut vector).insert(position, item);
+                self.stack.push(Value::Vector(vector));
+            },
+            Builtin::Remove => {
+                let index = self.pop_index()?;
+                let mut vector = check_types!(self.pop()?, Vector);
+                let position = from_end(index, vector.len())?;
+                self.stack.push(Rc::make_mut(&mut vector).remove(position));
+            },
+            Builtin::IntoVec => {
+                let function = check_types!(self.pop()?, Function);
+                let start = self.stack.len();
+                let mut inner = self.clone();
+                inner.call(&function)?;
+                if inner.stack.len() < start {
+                    return Err(Error::StackUnderflow);
+                }
+                self.stack.push(Value::Vector(Rc::new(inner.stack.split_off(start))));
             },
-            "cast" => {
+            Builtin::Use => {
+                let file_name = check_types!(self.pop()?, String);
+                let text = std::fs::read_to_string(&file_name)
+                    .map_err(|source| Error::Io { path: file_name.clone(), source })?;
+                let code = self.compile(&parse(lex(text, &file_name)?)?)?;
+                let root = self.root.clone();
+                self.run_frame(&root, &code)?;
+            },
+            Builtin::Math(function) => math::execute(function, self)?,
+            Builtin::Text(function) => text::execute(function, self)?,
+            Builtin::Map(function) => map::execute(function, self)?,
+            Builtin::Vector(function) => vector::execute(function, self)?,
+            Builtin::Cast | Builtin::CheckedCast => {
                 let target = check_types!(self.pop()?, String);
                 let item = self.pop()?;
-                self.stack.push(item.cast_to(target)?);
+                let mode = if builtin == Builtin::Cast { CastMode::Truncate } else { CastMode::Checked };
+                self.stack.push(item.cast_to(&target, mode)?);
             }
-            _ => return Err(format!("Unknown builtin: {}", key))
         }
         Ok(())
     }
 
-    fn execute_node(&mut self, node: &Node) -> Result<(), String> {
-        match node {
-            Node::Tok(Token::Number(number)) => self.stack.push(Value::Number(*number)),
-            Node::Tok(Token::String(string)) => self.stack.push(Value::String(string.clone())),
-            Node::Tok(Token::OpenBracket) | Node::Tok(Token::CloseBracket) => return Err("parsing error: bracket appeared in ast".to_string()),
-            Node::Group(inner) => self.stack.push(Value::Function(inner.to_vec())),
-            Node::Tok(Token::Identifier(ident)) => {
-                if BUILTINS.contains(&ident.as_str()) {
-                    self.execute_builtin(ident)?;
-                } else {
-                    let global_func = self.global.get(ident).cloned();
-                    let scoped_func = self.scoped_get(ident);
-                    let func = scoped_func
-                        .or(global_func)
-                        .ok_or(format!("Unknown function: {}", ident))?;
-                    let inner = check_type!(func, Function)?;
-                    self.execute_function(&inner)?;
-                }
+    fn execute_op(&mut self, op: &Op) -> Result<(), Error> {
+        match op {
+            Op::Push(value) => self.stack.push(value.clone()),
+            Op::Quote(code) => self.stack.push(Value::Function(Closure {
+                code: code.clone(),
+                scope: self.scope.clone()
+            })),
+            Op::Map(code) => {
+                let items = self.run_items(code)?;
+                self.stack.push(Value::Map(Rc::new(map::from_items(items)?)));
             },
+            Op::List(code) => {
+                let items = self.run_items(code)?;
+                self.stack.push(Value::Vector(Rc::new(items)));
+            },
+            Op::Builtin(builtin) => self.execute_builtin(*builtin)?,
+            Op::Word(id) => self.execute_word(*id)?
         }
         Ok(())
     }
 
-    fn scoped_get(&self, key: &String) -> Option<Value> {
-        self.scoped.get(key)
-            .map(|item| item.value.clone())
+    /// Runs the body of a literal, taking everything it pushed.
+    fn run_items(&mut self, code: &Code) -> Result<Vec<Value>, Error> {
+        let start = self.stack.len();
+        self.run(code)?;
+        if self.stack.len() < start {
+            return Err(Error::StackUnderflow);
+        }
+        Ok(self.stack.split_off(start))
+    }
 
+    fn execute_word(&mut self, id: usize) -> Result<(), Error> {
+        if let Some(Some(word)) = self.native.get(id) {
+            let word = word.clone();
+            return self.execute_native(id, word);
+        }
+        let func = self.scoped_get(id)
+            .or_else(|| self.global_get(id))
+            .ok_or_else(|| Error::UnknownWord(self.words.name(id).to_string()))?;
+        let inner = check_type!(func, Function)?;
+        self.call(&inner)
+    }
+
+    fn execute_native(&mut self, id: usize, word: NativeWord) -> Result<(), Error> {
+        let start = self.stack.len()
+            .checked_sub(word.effect.inputs)
+            .ok_or(Error::StackUnderflow)?;
+        let inputs = self.stack.split_off(start);
+        let outputs = (word.function)(inputs)?;
+        if outputs.len() != word.effect.outputs {
+            return Err(Error::StackEffectMismatch {
+                word: self.words.name(id).to_string(),
+                expected: word.effect.outputs,
+                found: outputs.len()
+            });
+        }
+        self.stack.extend(outputs);
+        Ok(())
+    }
+
+    fn scoped_get(&self, id: usize) -> Option<Value> {
+        self.scope.get(id)
+    }
+
+    pub(crate) fn global_get(&self, id: usize) -> Option<Value> {
+        self.global.get(id)?.clone()
     }
 
 }
 
-pub fn run_tree(tree: Vec<Node>) -> Result<(), String> {
-    let mut state = State {
-        stack: vec![],
-        global: HashMap::new(),
-        scoped: HashMap::new()
-    };
-    state.execute_function(&tree)?;
-    Ok(())
+pub fn run_tree(tree: Vec<Node>) -> Result<Vec<Value>, Error> {
+    let mut state = State::default();
+    let code = state.compile(&tree)?;
+    state.run(&code)?;
+    Ok(state.stack)
 }
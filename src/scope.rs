@@ -0,0 +1,70 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::value::Value;
+
+/// How many references to `scope` `value` holds that nothing else can reach:
+/// closures capturing it, directly or inside vectors and maps only `value`
+/// owns.
+fn captures(value: &Value, scope: &Rc<Scope>) -> usize {
+    match value {
+        Value::Function(closure) => Rc::ptr_eq(&closure.scope, scope) as usize,
+        Value::Vector(items) if Rc::strong_count(items) == 1 => {
+            items.iter().map(|item| captures(item, scope)).sum()
+        },
+        Value::Map(entries) if Rc::strong_count(entries) == 1 => {
+            entries.values().map(|item| captures(item, scope)).sum()
+        },
+        _ => 0
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Scope {
+    variables: RefCell<HashMap<usize, Value>>,
+    parent: Option<Rc<Scope>>
+}
+
+impl Scope {
+    pub(crate) fn child(parent: &Rc<Scope>) -> Rc<Scope> {
+        Rc::new(Scope {
+            variables: RefCell::default(),
+            parent: Some(parent.clone())
+        })
+    }
+
+    /// A frame with the same bindings and parent, whose later bindings are
+    /// not shared with this one.
+    pub(crate) fn copy(&self) -> Rc<Scope> {
+        Rc::new(Scope {
+            variables: RefCell::new(self.variables.borrow().clone()),
+            parent: self.parent.clone()
+        })
+    }
+
+    /// Called when a word returns. A closure bound in its own frame keeps
+    /// that frame alive through a reference cycle, so if those closures are
+    /// the only references left, the bindings are dropped to free it.
+    pub(crate) fn release(self: Rc<Scope>) {
+        let internal: usize = self.variables.borrow()
+            .values()
+            .map(|value| captures(value, &self))
+            .sum();
+        if Rc::strong_count(&self) == internal + 1 {
+            self.variables.borrow_mut().clear();
+        }
+    }
+
+    pub(crate) fn bind(&self, id: usize, value: Value) {
+        self.variables.borrow_mut().insert(id, value);
+    }
+
+    pub(crate) fn get(&self, id: usize) -> Option<Value> {
+        let mut scope = self;
+        loop {
+            if let Some(value) = scope.variables.borrow().get(&id) {
+                return Some(value.clone());
+            }
+            scope = scope.parent.as_deref()?;
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::{fmt, rc::Rc};
+
+#[derive(Debug, PartialEq)]
+pub struct Source {
+    pub name: String,
+    pub text: String
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Span {
+    pub source: Rc<Source>,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize
+}
+
+impl Span {
+    pub fn excerpt(&self) -> String {
+        let line_text = self.source.text
+            .lines()
+            .nth(self.line - 1)
+            .unwrap_or("");
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        format!(
+            "{gutter} |\n{number} | {line_text}\n{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.length.max(1))
+        )
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.source.name, self.line, self.column)
+    }
+}
+
+impl fmt::Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
@@ -0,0 +1,130 @@
+use std::rc::Rc;
+
+use crate::{
+    error::Error,
+    number::Number,
+    runtime::State,
+    value::Value
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Text {
+    Concat, Len, Slice, Split, Join, Trim, Upper, Lower,
+    Contains, StartsWith, EndsWith, Replace, Chars, Find, RepeatStr
+}
+
+/// `concat`, `len` and `slice` are looked up as vector words, which hand
+/// strings over to these.
+pub(crate) const WORDS: &[(&str, Text)] = &[
+    ("split", Text::Split), ("join", Text::Join), ("trim", Text::Trim),
+    ("upper", Text::Upper), ("lower", Text::Lower),
+    ("contains", Text::Contains), ("starts_with", Text::StartsWith), ("ends_with", Text::EndsWith),
+    ("replace", Text::Replace), ("chars", Text::Chars), ("find", Text::Find), ("repeat_str", Text::RepeatStr)
+];
+
+impl Text {
+    pub(crate) fn from_name(name: &str) -> Option<Text> {
+        WORDS.iter()
+            .find(|(word_name, _)| *word_name == name)
+            .map(|(_, function)| *function)
+    }
+}
+
+fn length(count: usize) -> Value {
+    Value::Number(Number::U64(count as u64))
+}
+
+fn strings(items: Vec<String>) -> Value {
+    Value::Vector(Rc::new(items.into_iter().map(Value::String).collect()))
+}
+
+/// The byte offset of the char at `index`, where `index` may be one past the
+/// last char.
+fn byte_offset(text: &str, index: usize) -> Result<usize, Error> {
+    let length = text.chars().count();
+    if index > length {
+        return Err(Error::IndexOutOfRange { index, length });
+    }
+    Ok(text.char_indices().nth(index).map_or(text.len(), |(offset, _)| offset))
+}
+
+pub(crate) fn execute(function: Text, state: &mut State) -> Result<(), Error> {
+    let result = match function {
+        Text::Concat => {
+            let second = state.pop_string()?;
+            let first = state.pop_string()?;
+            Value::String(first + &second)
+        },
+        Text::Len => length(state.pop_string()?.chars().count()),
+        Text::Slice => {
+            let end = state.pop_index()?;
+            let start = state.pop_index()?;
+            let text = state.pop_string()?;
+            let end_offset = byte_offset(&text, end)?;
+            if start > end {
+                return Err(Error::InvalidRange { start, end });
+            }
+            Value::String(text[byte_offset(&text, start)?..end_offset].to_string())
+        },
+        // An empty separator splits on runs of whitespace and drops empty
+        // pieces.
+        Text::Split => {
+            let separator = state.pop_string()?;
+            let text = state.pop_string()?;
+            if separator.is_empty() {
+                strings(text.split_whitespace().map(str::to_string).collect())
+            } else {
+                strings(text.split(separator.as_str()).map(str::to_string).collect())
+            }
+        },
+        Text::Join => {
+            let separator = state.pop_string()?;
+            let items = state.pop_vector()?;
+            let parts = items.iter()
+                .map(|item| match item {
+                    Value::String(part) => Ok(part.as_str()),
+                    item => Err(Error::TypeMismatch { expected: "String", found: item.type_name() })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Value::String(parts.join(&separator))
+        },
+        Text::Trim => Value::String(state.pop_string()?.trim().to_string()),
+        Text::Upper => Value::String(state.pop_string()?.to_uppercase()),
+        Text::Lower => Value::String(state.pop_string()?.to_lowercase()),
+        Text::Contains | Text::StartsWith | Text::EndsWith => {
+            let needle = state.pop_string()?;
+            let text = state.pop_string()?;
+            Value::Boolean(match function {
+                Text::Contains => text.contains(&needle),
+                Text::StartsWith => text.starts_with(&needle),
+                _ => text.ends_with(&needle)
+            })
+        },
+        Text::Replace => {
+            let to = state.pop_string()?;
+            let from = state.pop_string()?;
+            let text = state.pop_string()?;
+            Value::String(text.replace(&from, &to))
+        },
+        Text::Chars => strings(state.pop_string()?.chars().map(String::from).collect()),
+        // Pushes the char index of the first match, then whether there was
+        // one. Without a match the index is the length in chars, like
+        // `find_index` on vectors.
+        Text::Find => {
+            let needle = state.pop_string()?;
+            let text = state.pop_string()?;
+            let found = text.find(&needle);
+            let index = found.map_or(text.chars().count(), |offset| text[..offset].chars().count());
+            state.stack.push(length(index));
+            Value::Boolean(found.is_some())
+        },
+        Text::RepeatStr => {
+            let count = state.pop_index()?;
+            let text = state.pop_string()?;
+            text.len().checked_mul(count).ok_or(Error::ArithmeticOverflow { operation: "repeat_str" })?;
+            Value::String(text.repeat(count))
+        }
+    };
+    state.stack.push(result);
+    Ok(())
+}
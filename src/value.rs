@@ -1,78 +1,160 @@
+use std::{fmt, rc::Rc};
+
 use crate::{
-    number::Number,
-    parser::Node
+    bytecode::Code,
+    error::Error,
+    map::Map,
+    number::{CastMode, Number},
+    scope::Scope
 };
 
+#[derive(Clone)]
+pub struct Closure {
+    pub(crate) code: Rc<Code>,
+    pub(crate) scope: Rc<Scope>
+}
+
+impl PartialEq for Closure {
+    fn eq(&self, other: &Closure) -> bool {
+        self.code == other.code && Rc::ptr_eq(&self.scope, &other.scope)
+    }
+}
+
+impl fmt::Debug for Closure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.code.fmt(f)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Number(Number),
     String(String),
     Boolean(bool),
-    Vector(Vec<Value>),
-    Function(Vec<Node>),
+    Vector(Rc<Vec<Value>>),
+    Map(Rc<Map>),
+    Function(Closure),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(value) => value.fmt(f),
+            Value::String(text) => f.write_str(text),
+            Value::Boolean(value) => value.fmt(f),
+            Value::Vector(items) => {
+                f.write_str("[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    match item {
+                        Value::String(text) => write!(f, "{:?}", text)?,
+                        item => write!(f, "{}", item)?
+                    }
+                }
+                f.write_str("]")
+            },
+            Value::Map(entries) => {
+                f.write_str("#{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    match value {
+                        Value::String(text) => write!(f, "{}: {:?}", key, text)?,
+                        value => write!(f, "{}: {}", key, value)?
+                    }
+                }
+                f.write_str("}")
+            },
+            Value::Function(_) => f.write_str("<function>")
+        }
+    }
 }
 
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        if let (Value::Number(num1), Value::Number(num2)) = (self, other) {
-            num1.partial_cmp(num2)
-        } else {
-            None
+        match (self, other) {
+            (Value::Number(num1), Value::Number(num2)) => num1.partial_cmp(num2),
+            (Value::String(text1), Value::String(text2)) => text1.partial_cmp(text2),
+            (Value::Boolean(bool1), Value::Boolean(bool2)) => bool1.partial_cmp(bool2),
+            _ => None
         }
     }
 }
 
 impl Value {
-    pub fn cast_to(&self, result_type: String) -> Result<Value, String> {
+    pub fn type_name(&self) -> &'static str {
         match self {
-            Value::Number(value) => Ok(Value::Number(value.cast_to(result_type)?)),
-            _ => Err("Cannot cast this type".to_string())
+            Value::Number(_) => "Number",
+            Value::String(_) => "String",
+            Value::Boolean(_) => "Boolean",
+            Value::Vector(_) => "Vector",
+            Value::Map(_) => "Map",
+            Value::Function(_) => "Function",
         }
     }
+
+    pub fn cast_to(&self, result_type: &str, mode: CastMode) -> Result<Value, Error> {
+        let invalid = |value: String| Error::InvalidCast { value, target: result_type.to_string() };
+        match (self, result_type) {
+            (Value::String(_), "string") | (Value::Boolean(_), "bool") => Ok(self.clone()),
+            (Value::Number(value), "string") => Ok(Value::String(value.to_string())),
+            (Value::Boolean(value), "string") => Ok(Value::String(value.to_string())),
+            (Value::Number(value), "bool") => Ok(Value::Boolean(!value.is_zero())),
+            (Value::String(text), "bool") => match text.as_str() {
+                "true" => Ok(Value::Boolean(true)),
+                "false" => Ok(Value::Boolean(false)),
+                _ => Err(invalid(text.clone()))
+            },
+            (Value::Number(value), _) => Ok(Value::Number(value.cast_to(result_type, mode)?)),
+            (Value::Boolean(value), _) => Ok(Value::Number(Number::U8(*value as u8).cast_to(result_type, mode)?)),
+            (Value::String(text), _) => Ok(Value::Number(Number::parse(text, result_type, mode)?)),
+            (value, _) => Err(Error::TypeMismatch { expected: "Number", found: value.type_name() })
+        }
+    }
+}
+
+pub(crate) fn number_operands(v1: Value, v2: Value) -> Result<(Number, Number), Error> {
+    match (v1, v2) {
+        (Value::Number(v1), Value::Number(v2)) => Ok((v1, v2)),
+        (Value::Number(_), value) | (value, _) => Err(Error::TypeMismatch { expected: "Number", found: value.type_name() })
+    }
 }
 
 impl std::ops::Add for Value {
-    type Output = Result<Self, String>;
-    fn add(self, other: Value) -> Result<Self, String> {
-        match (self, other) {
-            (Value::Number(v1), Value::Number(v2)) => Ok(Value::Number((v1+v2)?)),
-            _ => Err("Invalid types for '+'".to_string())
-        }
+    type Output = Result<Self, Error>;
+    fn add(self, other: Value) -> Result<Self, Error> {
+        let (v1, v2) = number_operands(self, other)?;
+        Ok(Value::Number((v1+v2)?))
     }
 }
 impl std::ops::Sub for Value {
-    type Output = Result<Self, String>;
-    fn sub(self, other: Value) -> Result<Self, String> {
-        match (self, other) {
-            (Value::Number(v1), Value::Number(v2)) => Ok(Value::Number((v1-v2)?)),
-            _ => Err("Invalid types for '-'".to_string())
-        }
+    type Output = Result<Self, Error>;
+    fn sub(self, other: Value) -> Result<Self, Error> {
+        let (v1, v2) = number_operands(self, other)?;
+        Ok(Value::Number((v1-v2)?))
     }
 }
 impl std::ops::Mul for Value {
-    type Output = Result<Self, String>;
-    fn mul(self, other: Value) -> Result<Self, String> {
-        match (self, other) {
-            (Value::Number(v1), Value::Number(v2)) => Ok(Value::Number((v1*v2)?)),
-            _ => Err("Invalid types for '*'".to_string())
-        }
+    type Output = Result<Self, Error>;
+    fn mul(self, other: Value) -> Result<Self, Error> {
+        let (v1, v2) = number_operands(self, other)?;
+        Ok(Value::Number((v1*v2)?))
     }
 }
 impl std::ops::Div for Value {
-    type Output = Result<Self, String>;
-    fn div(self, other: Value) -> Result<Self, String> {
-        match (self, other) {
-            (Value::Number(v1), Value::Number(v2)) => Ok(Value::Number((v1/v2)?)),
-            _ => Err("Invalid types for '/'".to_string())
-        }
+    type Output = Result<Self, Error>;
+    fn div(self, other: Value) -> Result<Self, Error> {
+        let (v1, v2) = number_operands(self, other)?;
+        Ok(Value::Number((v1/v2)?))
     }
 }
 impl std::ops::Rem for Value {
-    type Output = Result<Self, String>;
-    fn rem(self, other: Value) -> Result<Self, String> {
-        match (self, other) {
-            (Value::Number(v1), Value::Number(v2)) => Ok(Value::Number((v1%v2)?)),
-            _ => Err("Invalid types for '%'".to_string())
-        }
+    type Output = Result<Self, Error>;
+    fn rem(self, other: Value) -> Result<Self, Error> {
+        let (v1, v2) = number_operands(self, other)?;
+        Ok(Value::Number((v1%v2)?))
     }
 }
@@ -0,0 +1,223 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{
+    error::Error,
+    number::Number,
+    runtime::State,
+    text::{self, Text},
+    value::{Closure, Value}
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Vector {
+    Len, Get, Set, Push, Pop, Concat, Reverse, Slice, Sort, SortBy,
+    Map, Filter, Fold, Zip, Enumerate, Any, All, FindIndex
+}
+
+pub(crate) const WORDS: &[(&str, Vector)] = &[
+    ("len", Vector::Len), ("get", Vector::Get), ("set", Vector::Set),
+    ("push", Vector::Push), ("pop", Vector::Pop), ("concat", Vector::Concat),
+    ("reverse", Vector::Reverse), ("slice", Vector::Slice), ("sort", Vector::Sort), ("sort_by", Vector::SortBy),
+    ("map", Vector::Map), ("filter", Vector::Filter), ("fold", Vector::Fold), ("zip", Vector::Zip),
+    ("enumerate", Vector::Enumerate), ("any", Vector::Any), ("all", Vector::All), ("find_index", Vector::FindIndex)
+];
+
+impl Vector {
+    pub(crate) fn from_name(name: &str) -> Option<Vector> {
+        WORDS.iter()
+            .find(|(word_name, _)| *word_name == name)
+            .map(|(_, function)| *function)
+    }
+}
+
+fn index(value: usize) -> Value {
+    Value::Number(Number::U64(value as u64))
+}
+
+fn vector(items: Vec<Value>) -> Value {
+    Value::Vector(Rc::new(items))
+}
+
+fn check_index(index: usize, length: usize) -> Result<usize, Error> {
+    if index < length {
+        Ok(index)
+    } else {
+        Err(Error::IndexOutOfRange { index, length })
+    }
+}
+
+/// Whether the value `depth` places below the top of the stack is a string,
+/// for the words shared with strings.
+fn is_string(state: &State, depth: usize) -> bool {
+    matches!(state.stack.iter().rev().nth(depth), Some(Value::String(_)))
+}
+
+fn compare(first: &Value, second: &Value) -> Result<Ordering, Error> {
+    first.partial_cmp(second).ok_or_else(|| if first.type_name() != second.type_name() {
+        Error::TypeMismatch { expected: first.type_name(), found: second.type_name() }
+    } else {
+        Error::OutOfDomain { operation: "sort", value: first.to_string() }
+    })
+}
+
+/// A stable merge sort, since `less` can fail and a user-supplied ordering
+/// need not be consistent.
+fn merge_sort(
+    mut items: Vec<Value>,
+    less: &mut impl FnMut(&Value, &Value) -> Result<bool, Error>
+) -> Result<Vec<Value>, Error> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let right = items.split_off(items.len() / 2);
+    let left = merge_sort(items, less)?;
+    let right = merge_sort(right, less)?;
+    let mut result = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(first), Some(second)) = (left.peek(), right.peek()) {
+        if less(second, first)? {
+            result.extend(right.next());
+        } else {
+            result.extend(left.next());
+        }
+    }
+    result.extend(left.chain(right));
+    Ok(result)
+}
+
+/// Calls `function` on `items` the way `call` would, in a frame of its own.
+fn apply(state: &mut State, function: &Closure, items: &[&Value]) -> Result<Value, Error> {
+    state.stack.extend(items.iter().map(|item| (*item).clone()));
+    state.call(function)?;
+    state.pop()
+}
+
+fn test(state: &mut State, function: &Closure, item: &Value) -> Result<bool, Error> {
+    match apply(state, function, &[item])? {
+        Value::Boolean(result) => Ok(result),
+        value => Err(Error::TypeMismatch { expected: "Boolean", found: value.type_name() })
+    }
+}
+
+pub(crate) fn execute(function: Vector, state: &mut State) -> Result<(), Error> {
+    let result = match function {
+        Vector::Len if is_string(state, 0) => return text::execute(Text::Len, state),
+        Vector::Concat if is_string(state, 0) => return text::execute(Text::Concat, state),
+        Vector::Slice if is_string(state, 2) => return text::execute(Text::Slice, state),
+        Vector::Len => index(state.pop_vector()?.len()),
+        Vector::Get => {
+            let position = state.pop_index()?;
+            let items = state.pop_vector()?;
+            items[check_index(position, items.len())?].clone()
+        },
+        Vector::Set => {
+            let item = state.pop()?;
+            let position = state.pop_index()?;
+            let mut items = state.pop_vector()?;
+            let position = check_index(position, items.len())?;
+            Rc::make_mut(&mut items)[position] = item;
+            Value::Vector(items)
+        },
+        Vector::Push => {
+            let item = state.pop()?;
+            let mut items = state.pop_vector()?;
+            Rc::make_mut(&mut items).push(item);
+            Value::Vector(items)
+        },
+        Vector::Pop => Rc::unwrap_or_clone(state.pop_vector()?).pop()
+            .ok_or(Error::IndexOutOfRange { index: 0, length: 0 })?,
+        Vector::Concat => {
+            let second = state.pop_vector()?;
+            let mut first = state.pop_vector()?;
+            Rc::make_mut(&mut first).extend(second.iter().cloned());
+            Value::Vector(first)
+        },
+        Vector::Reverse => {
+            let mut items = state.pop_vector()?;
+            Rc::make_mut(&mut items).reverse();
+            Value::Vector(items)
+        },
+        Vector::Slice => {
+            let end = state.pop_index()?;
+            let start = state.pop_index()?;
+            let items = state.pop_vector()?;
+            if end > items.len() {
+                return Err(Error::IndexOutOfRange { index: end, length: items.len() });
+            }
+            if start > end {
+                return Err(Error::InvalidRange { start, end });
+            }
+            vector(items[start..end].to_vec())
+        },
+        Vector::Sort => {
+            let items = Rc::unwrap_or_clone(state.pop_vector()?);
+            vector(merge_sort(items, &mut |first, second| Ok(compare(first, second)? == Ordering::Less))?)
+        },
+        Vector::SortBy => {
+            let less = state.pop_function()?;
+            let items = Rc::unwrap_or_clone(state.pop_vector()?);
+            vector(merge_sort(items, &mut |first, second| match apply(state, &less, &[first, second])? {
+                Value::Boolean(result) => Ok(result),
+                value => Err(Error::TypeMismatch { expected: "Boolean", found: value.type_name() })
+            })?)
+        },
+        Vector::Map => {
+            let function = state.pop_function()?;
+            let items = state.pop_vector()?;
+            vector(items.iter().map(|item| apply(state, &function, &[item])).collect::<Result<_, _>>()?)
+        },
+        Vector::Filter => {
+            let predicate = state.pop_function()?;
+            let mut kept = vec![];
+            for item in Rc::unwrap_or_clone(state.pop_vector()?) {
+                if test(state, &predicate, &item)? {
+                    kept.push(item);
+                }
+            }
+            vector(kept)
+        },
+        Vector::Fold => {
+            let function = state.pop_function()?;
+            let mut accumulator = state.pop()?;
+            for item in state.pop_vector()?.iter() {
+                accumulator = apply(state, &function, &[&accumulator, item])?;
+            }
+            accumulator
+        },
+        Vector::Zip => {
+            let second = state.pop_vector()?;
+            let first = state.pop_vector()?;
+            vector(first.iter().zip(second.iter())
+                .map(|(first, second)| vector(vec![first.clone(), second.clone()]))
+                .collect())
+        },
+        Vector::Enumerate => {
+            let items = state.pop_vector()?;
+            vector(items.iter().enumerate()
+                .map(|(position, item)| vector(vec![index(position), item.clone()]))
+                .collect())
+        },
+        Vector::Any | Vector::All | Vector::FindIndex => {
+            let predicate = state.pop_function()?;
+            let items = state.pop_vector()?;
+            let mut found = None;
+            for (position, item) in items.iter().enumerate() {
+                if test(state, &predicate, item)? != (function == Vector::All) {
+                    found = Some(position);
+                    break;
+                }
+            }
+            match function {
+                Vector::Any => Value::Boolean(found.is_some()),
+                Vector::All => Value::Boolean(found.is_none()),
+                _ => {
+                    state.stack.push(index(found.unwrap_or(items.len())));
+                    Value::Boolean(found.is_some())
+                }
+            }
+        }
+    };
+    state.stack.push(result);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::run_typed;
+use conc::Error;
+
+fn error(source: &str) -> Error {
+    run_typed(source).unwrap_err()
+}
+
+#[test]
+fn checked_arithmetic_keeps_the_type() {
+    assert_eq!(run_typed("u8_200 u8_55 + i16_-7 i16_2 / i32_-7 i32_2 %").unwrap(), ["u8 255", "i16 -3", "i32 -1"]);
+}
+
+#[test]
+fn overflow_is_an_error() {
+    assert!(matches!(error("u8_255 u8_1 +").root(), Error::ArithmeticOverflow { .. }));
+    assert!(matches!(error("u8_0 u8_1 -").root(), Error::ArithmeticOverflow { .. }));
+    assert!(matches!(error("i64_4611686018427387904 i64_2 *").root(), Error::ArithmeticOverflow { .. }));
+}
+
+#[test]
+fn division_by_zero() {
+    assert!(matches!(error("u64_1 u64_0 /").root(), Error::DivisionByZero));
+    assert!(matches!(error("i32_1 i32_0 %").root(), Error::DivisionByZero));
+}
+
+#[test]
+fn signed_division_overflow() {
+    assert!(matches!(error("i8_-128 i8_-1 /").root(), Error::ArithmeticOverflow { .. }));
+}
+
+#[test]
+fn wrapping() {
+    assert_eq!(run_typed("u8_255 u8_1 wrapping_+ u8_0 u8_1 wrapping_- i8_64 i8_2 wrapping_*").unwrap(), ["u8 0", "u8 255", "i8 -128"]);
+}
+
+#[test]
+fn saturating() {
+    assert_eq!(run_typed("u8_250 u8_10 saturating_+ i8_-100 i8_100 saturating_- u16_300 u16_300 saturating_*").unwrap(), ["u8 255", "i8 -128", "u16 65535"]);
+}
+
+#[test]
+fn overflowing() {
+    assert_eq!(run_typed("u8_255 u8_2 overflowing_+ u8_5 u8_3 overflowing_-").unwrap(), ["u8 1", "true", "u8 2", "false"]);
+    assert_eq!(run_typed("i8_-128 i8_-1 overflowing_*").unwrap(), ["i8 -128", "true"]);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{run, run_typed};
+use conc::{Error, bigint::BigInt, rational::Rational};
+
+fn big(text: &str) -> BigInt {
+    BigInt::parse(text).unwrap()
+}
+
+#[test]
+fn carries_across_limbs() {
+    let u64_max = u64::MAX.to_string();
+    assert_eq!((&big(&u64_max) + &big("1")).to_string(), "18446744073709551616");
+    assert_eq!((&big("18446744073709551616") - &big("1")).to_string(), u64_max);
+    assert_eq!(
+        (&big("340282366920938463463374607431768211455") + &big("1")).to_string(),
+        "340282366920938463463374607431768211456"
+    );
+    assert_eq!(
+        (&big(&u64_max) * &big(&u64_max)).to_string(),
+        "340282366920938463426481119284349108225"
+    );
+    assert_eq!(run_typed("big_18446744073709551615 big_1 + big_1 -").unwrap(), ["big 18446744073709551615"]);
+}
+
+#[test]
+fn div_rem_truncates_toward_zero() {
+    let cases = [("7", "2", "3", "1"), ("-7", "2", "-3", "-1"), ("7", "-2", "-3", "1"), ("-7", "-2", "3", "-1")];
+    for (dividend, divisor, quotient, remainder) in cases {
+        let (q, r) = big(dividend).div_rem(&big(divisor)).unwrap();
+        assert_eq!((q.to_string(), r.to_string()), (quotient.to_string(), remainder.to_string()));
+    }
+    let (q, r) = big("-340282366920938463463374607431768211457").div_rem(&big("18446744073709551616")).unwrap();
+    assert_eq!((q.to_string(), r.to_string()), ("-18446744073709551616".to_string(), "-1".to_string()));
+    assert!(big("1").div_rem(&BigInt::zero()).is_none());
+}
+
+#[test]
+fn literals() {
+    let stack = run_typed("big_-123456789012345678901234567890 ratio_6/4 ratio_-3 ratio_2/-4").unwrap();
+    assert_eq!(stack, ["big -123456789012345678901234567890", "ratio 3/2", "ratio -3", "ratio -1/2"]);
+    assert!(run("ratio_1/0").is_err());
+}
+
+#[test]
+fn whole_ratios_print_without_a_denominator() {
+    assert_eq!(Rational::parse("4/2").unwrap().to_string(), "2");
+    assert_eq!(run_typed("ratio_1/2 ratio_1/2 +").unwrap(), ["ratio 1"]);
+    assert_eq!(run_typed(r#"ratio_3/1 "string" cast "ratio" cast"#).unwrap(), ["ratio 3"]);
+}
+
+#[test]
+fn casts_to_and_from_fixed_width() {
+    let stack = run_typed(r#"u64_18446744073709551615 "big" cast i8_-5 "big" cast u8_3 "ratio" cast"#).unwrap();
+    assert_eq!(stack, ["big 18446744073709551615", "big -5", "ratio 3"]);
+    let stack = run_typed(r#"big_255 "u8" cast big_-128 "i8" cast ratio_7/2 "i32" cast ratio_6/3 "u8" checked_cast"#).unwrap();
+    assert_eq!(stack, ["u8 255", "i8 -128", "i32 3", "u8 2"]);
+    assert!(matches!(run(r#"big_256 "u8" cast"#).unwrap_err().root(), Error::InvalidCast { .. }));
+    assert!(matches!(run(r#"ratio_7/2 "i32" checked_cast"#).unwrap_err().root(), Error::InvalidCast { .. }));
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{run, run_typed};
+use conc::Error;
+
+fn shift_error(source: &str) -> (String, u32) {
+    match run(source).unwrap_err().root() {
+        Error::ShiftOutOfRange { amount, bits } => (amount.clone(), *bits),
+        error => panic!("expected a shift out of range, got {:?}", error)
+    }
+}
+
+#[test]
+fn and_or_xor() {
+    let stack = run_typed("u8_12 u8_10 and u8_12 u8_10 or u8_12 u8_10 xor i8_-1 i8_5 and").unwrap();
+    assert_eq!(stack, ["u8 8", "u8 14", "u8 6", "i8 5"]);
+    assert_eq!(run_typed("true false and true false or true true xor").unwrap(), ["false", "true", "false"]);
+}
+
+#[test]
+fn bitwise_words_promote_integers() {
+    assert_eq!(run_typed("u8_255 u16_256 or").unwrap(), ["u16 511"]);
+}
+
+#[test]
+fn bitwise_words_reject_non_integers() {
+    let error = run("f64_1 f64_2 and").unwrap_err();
+    assert!(matches!(error.root(), Error::TypeMismatch { expected: "integer", found: "f64" }));
+    let error = run("big_1 u32_1 shl").unwrap_err();
+    assert!(matches!(error.root(), Error::TypeMismatch { expected: "integer", found: "big" }));
+}
+
+#[test]
+fn not() {
+    assert_eq!(run_typed("u8_0 not i32_0 not true not").unwrap(), ["u8 255", "i32 -1", "false"]);
+}
+
+#[test]
+fn shifts() {
+    let stack = run_typed("u8_1 u32_7 shl u8_129 u32_1 shl i8_-128 u32_7 shr u64_1 u8_63 shl u8_5 i64_0 shr").unwrap();
+    assert_eq!(stack, ["u8 128", "u8 2", "i8 -1", "u64 9223372036854775808", "u8 5"]);
+}
+
+#[test]
+fn shift_by_the_bit_width_or_more_is_out_of_range() {
+    assert_eq!(shift_error("u8_1 u32_8 shl"), ("8".to_string(), 8));
+    assert_eq!(shift_error("i64_1 u32_64 shr"), ("64".to_string(), 64));
+    assert_eq!(shift_error("u128_1 u64_18446744073709551615 shl"), ("18446744073709551615".to_string(), 128));
+}
+
+#[test]
+fn negative_shift_is_out_of_range() {
+    assert_eq!(shift_error("u8_1 i32_-1 shl"), ("-1".to_string(), 8));
+    assert_eq!(shift_error("i16_1 i8_-3 shr"), ("-3".to_string(), 16));
+}
+
+#[test]
+fn fractional_shift_is_a_cast_error() {
+    assert!(matches!(run("u8_1 f64_1.5 shl").unwrap_err().root(), Error::InvalidCast { .. }));
+}
+
+#[test]
+fn rotations_wrap_around() {
+    let stack = run_typed("u8_129 u32_1 rotl u8_129 u32_1 rotr u8_1 u32_9 rotl u16_1 u32_16 rotr").unwrap();
+    assert_eq!(stack, ["u8 3", "u8 192", "u8 2", "u16 1"]);
+}
+
+#[test]
+fn counting_bits() {
+    let stack = run_typed("u8_7 popcount i8_-1 popcount u16_1 leading_zeros u32_8 trailing_zeros u64_0 trailing_zeros").unwrap();
+    assert_eq!(stack, ["u32 3", "u32 8", "u32 15", "u32 3", "u32 64"]);
+}
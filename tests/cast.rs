@@ -0,0 +1,68 @@
+mod common;
+
+use common::{run, run_typed, string};
+use conc::Error;
+
+fn cast_error(source: &str) -> (String, String) {
+    match run(source).unwrap_err().root() {
+        Error::InvalidCast { value, target } => (value.clone(), target.clone()),
+        error => panic!("expected an invalid cast, got {:?}", error)
+    }
+}
+
+#[test]
+fn cast_truncates_floats() {
+    let stack = run_typed(r#"f64_2.7 "u8" cast f64_-2.7 "i32" cast f64_300.5 "u8" cast f64_-1.5 "u64" cast"#).unwrap();
+    assert_eq!(stack, vec!["u8 2", "i32 -2", "u8 255", "u64 0"]);
+}
+
+#[test]
+fn checked_cast_requires_a_whole_float_in_range() {
+    let stack = run_typed(r#"f64_42.0 "u8" checked_cast f32_-7 "i16" checked_cast"#).unwrap();
+    assert_eq!(stack, vec!["u8 42", "i16 -7"]);
+    assert_eq!(cast_error(r#"f64_2.7 "u8" checked_cast"#), ("2.7".to_string(), "u8".to_string()));
+    assert_eq!(cast_error(r#"f64_300 "u8" checked_cast"#).1, "u8");
+    assert_eq!(cast_error(r#"f64_-1 "u64" checked_cast"#).1, "u64");
+}
+
+#[test]
+fn integer_casts_are_range_checked_in_both_modes() {
+    assert_eq!(run_typed(r#"i64_-5 "i8" cast u16_200 "u8" checked_cast"#).unwrap(), vec!["i8 -5", "u8 200"]);
+    assert_eq!(cast_error(r#"u16_256 "u8" cast"#), ("256".to_string(), "u8".to_string()));
+    assert_eq!(cast_error(r#"i8_-1 "u32" checked_cast"#).1, "u32");
+}
+
+#[test]
+fn strings_parse_in_plain_form() {
+    let stack = run_typed(r#""42" "u8" cast "-17" "i64" cast "2.5" "f64" cast "1.25" "dec" cast"#).unwrap();
+    assert_eq!(stack, vec!["u8 42", "i64 -17", "f64 2.5", "dec 1.25"]);
+}
+
+#[test]
+fn strings_parse_in_prefixed_form() {
+    let stack = run_typed(r#""0xff" "u8" cast "1_000" "u32" cast "i8_-3" "i64" cast "f64_2.7" "u8" cast"#).unwrap();
+    assert_eq!(stack, vec!["u8 255", "u32 1000", "i64 -3", "u8 2"]);
+    assert_eq!(cast_error(r#""f64_2.7" "u8" checked_cast"#), ("2.7".to_string(), "u8".to_string()));
+}
+
+#[test]
+fn unparseable_strings_are_rejected() {
+    assert_eq!(cast_error(r#""twelve" "u8" cast"#), ("twelve".to_string(), "u8".to_string()));
+    assert_eq!(cast_error(r#""256" "u8" cast"#).1, "u8");
+    assert!(matches!(run(r#""1" "u7" cast"#).unwrap_err().root(), Error::UnknownType(name) if name == "u7"));
+}
+
+#[test]
+fn bool_and_int_round_trip() {
+    let stack = run_typed(r#"true "u8" cast false "i64" cast true "f64" cast u8_0 "bool" cast i32_-3 "bool" cast"#).unwrap();
+    assert_eq!(stack, vec!["u8 1", "i64 0", "f64 1", "false", "true"]);
+    assert_eq!(run_typed(r#""true" "bool" cast "false" "bool" cast"#).unwrap(), vec!["true", "false"]);
+    assert_eq!(cast_error(r#""yes" "bool" cast"#), ("yes".to_string(), "bool".to_string()));
+}
+
+#[test]
+fn numbers_cast_to_strings() {
+    let stack = run(r#"u8_7 "string" cast f64_2.5 "string" cast i16_-4 "string" cast dec_1.50 "string" cast true "string" cast"#).unwrap();
+    assert_eq!(stack, vec![string("7"), string("2.5"), string("-4"), string("1.50"), string("true")]);
+    assert_eq!(run_typed(r#"u8_7 "string" cast "u64" cast"#).unwrap(), vec!["u64 7"]);
+}
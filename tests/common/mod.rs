@@ -0,0 +1,42 @@
+#![allow(dead_code)]
+
+use std::rc::Rc;
+
+use conc::{Error, Interpreter, value::Value, number::Number};
+
+/// Evaluates `source` in a fresh interpreter and returns the final stack.
+pub fn run(source: &str) -> Result<Vec<Value>, Error> {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval(source)?;
+    Ok(interpreter.stack().to_vec())
+}
+
+pub fn u64(n: u64) -> Value {
+    Value::Number(Number::U64(n))
+}
+
+pub fn string(text: &str) -> Value {
+    Value::String(text.to_string())
+}
+
+pub fn vector(items: Vec<Value>) -> Value {
+    Value::Vector(Rc::new(items))
+}
+
+pub fn u64_vector(items: &[u64]) -> Value {
+    vector(items.iter().map(|n| u64(*n)).collect())
+}
+
+/// Numbers as `"<type> <value>"` and everything else in its display form,
+/// so assertions also check the result type, which `Value` equality
+/// ignores for numbers.
+pub fn typed(value: &Value) -> String {
+    match value {
+        Value::Number(number) => format!("{} {}", number.type_name(), number),
+        value => value.to_string()
+    }
+}
+
+pub fn run_typed(source: &str) -> Result<Vec<String>, Error> {
+    Ok(run(source)?.iter().map(typed).collect())
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{run, run_typed};
+use conc::{Error, decimal::{Decimal, RoundingMode}};
+
+fn rounded(mode: &str, value: &str) -> String {
+    run_typed(&format!(r#""{}" rounding_mode {} u32_0 round"#, mode, value)).unwrap().remove(0)
+}
+
+#[test]
+fn division_keeps_the_larger_scale() {
+    let stack = run_typed("dec_1 dec_2 / dec_1.00 dec_2 / dec_1 dec_3.000 / dec_-2 dec_3.0 /").unwrap();
+    assert_eq!(stack, ["dec 0", "dec 0.50", "dec 0.333", "dec -0.7"]);
+    assert!(matches!(run("dec_1 dec_0.0 /").unwrap_err().root(), Error::DivisionByZero));
+}
+
+#[test]
+fn rounding_modes() {
+    let cases = [
+        ("half_even", ["dec 2", "dec 2", "dec -2", "dec 3"]),
+        ("half_up", ["dec 3", "dec 2", "dec -3", "dec 3"]),
+        ("half_down", ["dec 2", "dec 2", "dec -2", "dec 3"]),
+        ("up", ["dec 3", "dec 3", "dec -3", "dec 3"]),
+        ("down", ["dec 2", "dec 2", "dec -2", "dec 2"]),
+        ("ceiling", ["dec 3", "dec 3", "dec -2", "dec 3"]),
+        ("floor", ["dec 2", "dec 2", "dec -3", "dec 2"])
+    ];
+    for (mode, expected) in cases {
+        let actual = ["dec_2.5", "dec_2.1", "dec_-2.5", "dec_2.6"].map(|value| rounded(mode, value));
+        assert_eq!(actual, expected, "{}", mode);
+    }
+}
+
+#[test]
+fn rounding_mode_applies_to_division() {
+    let stack = run_typed(r#"dec_2 dec_3 / "floor" rounding_mode dec_2.0 dec_3 / "up" rounding_mode dec_1.0 dec_3 /"#).unwrap();
+    assert_eq!(stack, ["dec 1", "dec 0.6", "dec 0.4"]);
+}
+
+#[test]
+fn unknown_rounding_mode() {
+    let error = run(r#""sideways" rounding_mode"#).unwrap_err();
+    assert!(matches!(error.root(), Error::UnknownRoundingMode(name) if name == "sideways"));
+}
+
+#[test]
+fn round_to_places() {
+    let stack = run_typed("dec_1.2345 u32_2 round dec_1.5 u32_3 round f64_2.675 u32_1 round ratio_2/3 u32_2 round u8_7 u32_2 round").unwrap();
+    assert_eq!(stack, ["dec 1.23", "dec 1.500", "f64 2.7", "ratio 67/100", "u8 7"]);
+}
+
+#[test]
+fn rescale() {
+    let value = Decimal::parse("-1.255").unwrap();
+    assert_eq!(value.rescale(2, RoundingMode::HalfEven).unwrap().to_string(), "-1.26");
+    assert_eq!(value.rescale(2, RoundingMode::HalfDown).unwrap().to_string(), "-1.25");
+    assert_eq!(value.rescale(2, RoundingMode::Ceiling).unwrap().to_string(), "-1.25");
+    assert_eq!(value.rescale(5, RoundingMode::Down).unwrap().to_string(), "-1.25500");
+    assert_eq!(value.rescale(0, RoundingMode::Floor).unwrap().to_string(), "-2");
+    assert!(Decimal::parse("1").unwrap().rescale(40, RoundingMode::Down).is_none());
+}
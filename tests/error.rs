@@ -0,0 +1,77 @@
+use std::{io, rc::Rc};
+
+use conc::{Error, Interpreter, span::{Source, Span}};
+
+fn eval_error(source: &str) -> Error {
+    Interpreter::new().eval_named(source, "test.cnc").unwrap_err()
+}
+
+#[test]
+fn runtime_error_points_at_the_failing_word() {
+    let error = eval_error("u64_1\nu64_2 u64_0 /");
+    assert!(matches!(error.root(), Error::DivisionByZero));
+    let span = error.span().unwrap();
+    assert_eq!((span.line, span.column, span.length), (2, 13, 1));
+    assert_eq!(span.to_string(), "test.cnc:2:13");
+    assert_eq!(span.excerpt(), "  |\n2 | u64_2 u64_0 /\n  |             ^");
+}
+
+#[test]
+fn error_inside_a_called_word_points_into_its_body() {
+    let error = eval_error(concat!(
+        "{\n",
+        "    u64_0 /\n",
+        "} \"inner\" global_bind\n",
+        "{ inner } \"outer\" global_bind\n",
+        "u64_1 outer\n"
+    ));
+    assert!(matches!(error.root(), Error::DivisionByZero));
+    let span = error.span().unwrap();
+    assert_eq!((span.line, span.column, span.length), (2, 11, 1));
+    assert_eq!(span.excerpt(), "  |\n2 |     u64_0 /\n  |           ^");
+}
+
+fn span() -> Span {
+    let source = Rc::new(Source { name: "test.cnc".to_string(), text: "x".to_string() });
+    Span { source, line: 1, column: 1, length: 1 }
+}
+
+#[test]
+fn every_error_has_its_own_exit_code() {
+    let text = || "x".to_string();
+    let errors = [
+        (Error::LexError { message: text(), span: span(), unclosed: false }, 3),
+        (Error::ParseError { message: text(), span: span() }, 4),
+        (Error::StackUnderflow, 5),
+        (Error::TypeMismatch { expected: "u64", found: "String" }, 6),
+        (Error::UnknownWord(text()), 7),
+        (Error::UnknownVariable(text()), 8),
+        (Error::UnknownType(text()), 9),
+        (Error::InvalidCast { value: text(), target: text() }, 10),
+        (Error::IndexOutOfRange { index: 1, length: 0 }, 11),
+        (Error::ArithmeticOverflow { operation: "+" }, 12),
+        (Error::DivisionByZero, 13),
+        (Error::Io { path: text(), source: io::Error::from(io::ErrorKind::NotFound) }, 14),
+        (Error::WordAlreadyDefined(text()), 15),
+        (Error::StackEffectMismatch { word: text(), expected: 1, found: 0 }, 16),
+        (Error::UnknownRoundingMode(text()), 17),
+        (Error::OutOfDomain { operation: "sqrt", value: text() }, 18),
+        (Error::InvalidFormat(text()), 19),
+        (Error::MissingKey(text()), 20),
+        (Error::UnpairedMapKey(text()), 21),
+        (Error::ShiftOutOfRange { amount: text(), bits: 8 }, 22),
+        (Error::InvalidRange { start: 2, end: 1 }, 23)
+    ];
+    for (error, code) in errors {
+        assert_eq!(error.exit_code(), code, "{:?}", error);
+        assert_eq!(error.at(&span()).exit_code(), code);
+    }
+}
+
+#[test]
+fn multiple_errors_exit_with_the_first_code() {
+    let error = Error::Multiple(vec![Error::DivisionByZero, Error::StackUnderflow]);
+    assert_eq!(error.exit_code(), 13);
+    assert_eq!(Error::Multiple(vec![]).exit_code(), 1);
+    assert_eq!(eval_error("{ ]").exit_code(), 4);
+}
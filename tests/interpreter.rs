@@ -0,0 +1,95 @@
+mod common;
+
+use common::{string, u64};
+use conc::{Error, Interpreter, StackEffect, number::Number, value::Value};
+
+fn effect(inputs: usize, outputs: usize) -> StackEffect {
+    StackEffect { inputs, outputs }
+}
+
+fn sum_and_difference(inputs: Vec<Value>) -> Result<Vec<Value>, Error> {
+    match inputs[..] {
+        [Value::Number(Number::U64(first)), Value::Number(Number::U64(second))] => {
+            Ok(vec![u64(first + second), u64(first - second)])
+        },
+        _ => Err(Error::TypeMismatch { expected: "u64", found: "other" })
+    }
+}
+
+#[test]
+fn native_word_round_trip() {
+    let mut interpreter = Interpreter::new();
+    interpreter.register("sum_and_difference", effect(2, 2), sum_and_difference).unwrap();
+    interpreter.eval("u64_1 u64_7 u64_3 sum_and_difference").unwrap();
+    assert_eq!(interpreter.stack(), [u64(1), u64(10), u64(4)]);
+}
+
+#[test]
+fn native_word_underflow() {
+    let mut interpreter = Interpreter::new();
+    interpreter.register("sum_and_difference", effect(2, 2), sum_and_difference).unwrap();
+    let error = interpreter.eval("u64_1 sum_and_difference").unwrap_err();
+    assert!(matches!(error.root(), Error::StackUnderflow));
+}
+
+#[test]
+fn native_word_must_match_its_stack_effect() {
+    let mut interpreter = Interpreter::new();
+    interpreter.register("liar", effect(0, 2), |_| Ok(vec![u64(1)])).unwrap();
+    let error = interpreter.eval("liar").unwrap_err();
+    assert!(matches!(error.root(), Error::StackEffectMismatch { word, expected: 2, found: 1 } if word == "liar"));
+}
+
+#[test]
+fn builtins_cannot_be_registered_or_set() {
+    let mut interpreter = Interpreter::new();
+    for name in ["swap", "+", "map", "upper", "sqrt", "map_get"] {
+        let error = interpreter.register(name, effect(0, 0), |_| Ok(vec![])).unwrap_err();
+        assert!(matches!(error, Error::WordAlreadyDefined(word) if word == name));
+        let error = interpreter.set_global(name, u64(1)).unwrap_err();
+        assert!(matches!(error, Error::WordAlreadyDefined(word) if word == name));
+    }
+}
+
+#[test]
+fn registering_a_name_twice_is_an_error() {
+    let mut interpreter = Interpreter::new();
+    interpreter.register("answer", effect(0, 1), |_| Ok(vec![u64(42)])).unwrap();
+    let error = interpreter.register("answer", effect(0, 1), |_| Ok(vec![u64(0)])).unwrap_err();
+    assert!(matches!(error, Error::WordAlreadyDefined(word) if word == "answer"));
+    interpreter.eval("answer").unwrap();
+    assert_eq!(interpreter.stack(), [u64(42)]);
+}
+
+#[test]
+fn globals() {
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.global("greeting").is_none());
+    interpreter.set_global("greeting", string("hello")).unwrap();
+    interpreter.eval(r#""greeting" global_get " world" concat "shout" global_bind"#).unwrap();
+    assert_eq!(interpreter.global("shout"), Some(string("hello world")));
+    interpreter.set_global("greeting", string("bye")).unwrap();
+    interpreter.eval(r#""greeting" global_get"#).unwrap();
+    assert_eq!(interpreter.stack(), [string("bye")]);
+}
+
+#[test]
+fn push_pop_and_peek() {
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.peek().is_none());
+    assert!(matches!(interpreter.pop().unwrap_err(), Error::StackUnderflow));
+    interpreter.push(u64(2));
+    interpreter.push(u64(3));
+    assert_eq!(interpreter.peek(), Some(&u64(3)));
+    interpreter.eval("*").unwrap();
+    assert_eq!(interpreter.pop().unwrap(), u64(6));
+    assert!(interpreter.stack().is_empty());
+}
+
+#[test]
+fn stack_persists_between_evaluations() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval("u64_2").unwrap();
+    interpreter.eval("u64_5 +").unwrap();
+    assert_eq!(interpreter.stack(), [u64(7)]);
+}
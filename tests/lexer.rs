@@ -0,0 +1,199 @@
+use conc::{lexer::{self, Token}, number::Number, Error};
+
+fn tokens(source: &str) -> Vec<Token> {
+    lexer::lex(source.to_string(), "test").unwrap().into_iter().map(|(token, _)| token).collect()
+}
+
+fn identifier(name: &str) -> Token {
+    Token::Identifier(name.to_string())
+}
+
+#[test]
+fn brackets_are_self_delimiting() {
+    assert_eq!(tokens("{u64_1 call}[]#{}"), vec![
+        Token::OpenBracket,
+        Token::Number(Number::U64(1)),
+        identifier("call"),
+        Token::CloseBracket,
+        Token::OpenList,
+        Token::CloseList,
+        Token::OpenMap,
+        Token::CloseBracket
+    ]);
+}
+
+#[test]
+fn quotes_are_self_delimiting() {
+    assert_eq!(tokens(r#"print"a"print r"b""#), vec![
+        identifier("print"),
+        Token::String("a".to_string()),
+        identifier("print"),
+        Token::String("b".to_string())
+    ]);
+}
+
+#[test]
+fn comments() {
+    assert_eq!(tokens("a ;skip;b // to the end\nc;x;"), vec![identifier("a"), identifier("b"), identifier("c")]);
+}
+
+#[test]
+fn slash_inside_a_word_is_not_a_comment() {
+    assert_eq!(tokens("a//b /"), vec![identifier("a//b"), identifier("/")]);
+}
+
+#[test]
+fn unclosed_comment_is_located() {
+    let error = lexer::lex("u64_1\n  ; open".to_string(), "test").unwrap_err();
+    match error {
+        Error::LexError { message, span, unclosed } => {
+            assert_eq!(message, lexer::UNCLOSED_COMMENT);
+            assert!(unclosed);
+            assert_eq!((span.line, span.column), (2, 3));
+        },
+        error => panic!("expected a lex error, found {:?}", error)
+    }
+}
+
+/// The type and value of a single literal, since `Number` equality would
+/// promote across types.
+fn literal(source: &str) -> (&'static str, String) {
+    match tokens(source).as_slice() {
+        [Token::Number(number)] => (number.type_name(), number.to_string()),
+        tokens => panic!("expected one number, found {:?}", tokens)
+    }
+}
+
+fn lex_error(source: &str) -> String {
+    match lexer::lex(source.to_string(), "test").unwrap_err() {
+        Error::LexError { message, .. } => message,
+        error => panic!("expected a lex error, found {:?}", error)
+    }
+}
+
+#[test]
+fn radix_literals() {
+    assert_eq!(literal("0xff"), ("u64", "255".to_string()));
+    assert_eq!(literal("0o17"), ("u64", "15".to_string()));
+    assert_eq!(literal("0b1010"), ("u64", "10".to_string()));
+    assert_eq!(literal("i16_-0x10"), ("i16", "-16".to_string()));
+    assert_eq!(literal("0xFFu8"), ("u8", "255".to_string()));
+}
+
+#[test]
+fn separators_and_exponents() {
+    assert_eq!(literal("1_000_000"), ("u64", "1000000".to_string()));
+    assert_eq!(literal("2.5e-3"), ("f64", "0.0025".to_string()));
+    assert_eq!(literal("1E3"), ("f64", "1000".to_string()));
+    assert_eq!(literal("f32_1_0.5"), ("f32", "10.5".to_string()));
+}
+
+#[test]
+fn suffixes() {
+    assert_eq!(literal("255u8"), ("u8", "255".to_string()));
+    assert_eq!(literal("-5i32"), ("i32", "-5".to_string()));
+    assert_eq!(literal("1.5f32"), ("f32", "1.5".to_string()));
+    assert_eq!(literal("2f64"), ("f64", "2".to_string()));
+}
+
+#[test]
+fn negative_bare_literals_are_i64() {
+    assert_eq!(literal("-3"), ("i64", "-3".to_string()));
+    assert_eq!(literal("-2.5"), ("f64", "-2.5".to_string()));
+}
+
+#[test]
+fn out_of_range_literals() {
+    assert_eq!(lex_error("u8_256"), "literal 'u8_256' out of range for u8");
+    assert_eq!(lex_error("256u8"), "literal '256u8' out of range for u8");
+    assert_eq!(lex_error("-1u32"), "literal '-1u32' out of range for u32");
+    assert_eq!(lex_error("f32_1e39"), "literal 'f32_1e39' out of range for f32");
+}
+
+#[test]
+fn malformed_literals() {
+    assert_eq!(lex_error("0b102"), "invalid number literal '0b102'");
+    assert_eq!(lex_error("0xZZ"), "invalid number literal '0xZZ'");
+    assert_eq!(lex_error("-0o"), "invalid number literal '-0o'");
+    assert_eq!(lex_error("12abc"), "invalid number literal '12abc'");
+    assert_eq!(lex_error("1.5x"), "invalid number literal '1.5x'");
+    assert_eq!(lex_error("1e5q"), "invalid number literal '1e5q'");
+    assert_eq!(lex_error("1_0dup"), "invalid number literal '1_0dup'");
+    assert_eq!(lex_error("1.5u8"), "integer suffix 'u8' on float literal '1.5u8'");
+}
+
+#[test]
+fn words_starting_with_digits_are_identifiers() {
+    assert_eq!(tokens("2dup 1+ 3rd 1.x 1e -2drop -"), vec![
+        identifier("2dup"),
+        identifier("1+"),
+        identifier("3rd"),
+        identifier("1.x"),
+        identifier("1e"),
+        identifier("-2drop"),
+        identifier("-")
+    ]);
+}
+
+fn string(source: &str) -> String {
+    match tokens(source).as_slice() {
+        [Token::String(text)] => text.clone(),
+        tokens => panic!("expected one string, found {:?}", tokens)
+    }
+}
+
+fn lex_error_at(source: &str) -> (String, usize, usize, usize) {
+    match lexer::lex(source.to_string(), "test").unwrap_err() {
+        Error::LexError { message, span, .. } => (message, span.line, span.column, span.length),
+        error => panic!("expected a lex error, found {:?}", error)
+    }
+}
+
+#[test]
+fn escapes() {
+    assert_eq!(string(r#""a\nb\tc\rd\0e\\f\"g""#), "a\nb\tc\rd\0e\\f\"g");
+    assert_eq!(string(r#""\u{41}\u{e9}\u{1F600}""#), "Aé😀");
+}
+
+#[test]
+fn invalid_escapes_are_located() {
+    assert_eq!(lex_error_at(r#"u64_1 "ab\qc""#), ("invalid escape '\\q'".to_string(), 1, 10, 2));
+    assert_eq!(lex_error_at("\"x\n  \\u{110000}\""), ("invalid unicode escape '\\u{110000}'".to_string(), 2, 3, 10));
+    assert_eq!(lex_error_at(r#""\u{41""#), ("invalid unicode escape '\\u{41'".to_string(), 1, 2, 5));
+}
+
+#[test]
+fn raw_strings() {
+    assert_eq!(string(r#"r"C:\path\n""#), r"C:\path\n");
+    assert_eq!(string(r###"r#"say "hi" \n"#"###), r#"say "hi" \n"#);
+    assert_eq!(string(r###"r##"a "# b"##"###), r##"a "# b"##);
+}
+
+#[test]
+fn unclosed_raw_string() {
+    assert_eq!(lex_error_at(r###"r#"never"###).0, lexer::UNCLOSED_STRING);
+}
+
+#[test]
+fn triple_quoted_strings_are_dedented() {
+    let source = "\"\"\"\n        first\n          indented\n\n        last\n    \"\"\"";
+    assert_eq!(string(source), "first\n  indented\n\nlast");
+}
+
+#[test]
+fn triple_quoted_strings_keep_escapes_and_quotes() {
+    assert_eq!(string(r#""""a "quoted" \t word""""#), "a \"quoted\" \t word");
+}
+
+#[test]
+fn only_unclosed_input_is_flagged_as_unclosed() {
+    let unclosed = |source: &str| match lexer::lex(source.to_string(), "test").unwrap_err() {
+        Error::LexError { unclosed, .. } => unclosed,
+        error => panic!("expected a lex error, found {:?}", error)
+    };
+    assert!(unclosed(r#""open"#));
+    assert!(unclosed(r#"""" open"#));
+    assert!(unclosed("; open"));
+    assert!(!unclosed(r#""bad \q""#));
+    assert!(!unclosed("u8_256"));
+}
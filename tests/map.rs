@@ -0,0 +1,70 @@
+mod common;
+
+use common::{run, string, u64, vector};
+use conc::{Error, value::Value};
+
+#[test]
+fn set_and_get() {
+    let stack = run(r#"map_new "a" u64_1 map_set "b" u64_2 map_set "b" map_get"#).unwrap();
+    assert_eq!(stack, vec![u64(2)]);
+}
+
+#[test]
+fn missing_key() {
+    let error = run(r#"map_new "a" map_get"#).unwrap_err();
+    assert!(matches!(error.root(), Error::MissingKey(key) if key == "\"a\""));
+}
+
+#[test]
+fn has_and_remove() {
+    let stack = run(r#"#{ "a" u64_1 "b" u64_2 } "a" map_remove clone "a" map_has swap "b" map_has"#).unwrap();
+    assert_eq!(stack, vec![Value::Boolean(false), Value::Boolean(true)]);
+}
+
+#[test]
+fn keys_and_values_are_sorted_by_key() {
+    let stack = run(r#"#{ "b" u64_2 "a" u64_1 } clone map_keys swap map_values"#).unwrap();
+    assert_eq!(stack, vec![
+        vector(vec![string("a"), string("b")]),
+        vector(vec![u64(1), u64(2)])
+    ]);
+}
+
+#[test]
+fn number_keys_are_distinguished_by_type() {
+    let stack = run(r#"#{ u64_1 "unsigned" i32_1 "signed" true "flag" } clone u64_1 map_get swap i32_1 map_get"#).unwrap();
+    assert_eq!(stack, vec![string("unsigned"), string("signed")]);
+}
+
+#[test]
+fn for_visits_every_entry() {
+    let stack = run(r#"u64_0 #{ "a" u64_1 "b" u64_2 } { swap delete + } map_for"#).unwrap();
+    assert_eq!(stack, vec![u64(3)]);
+}
+
+#[test]
+fn literal_elements_are_evaluated() {
+    let stack = run(r#"#{ "sum" u64_1 u64_2 + "nested" #{ "x" u64_0 } } "nested" map_get "x" map_get"#).unwrap();
+    assert_eq!(stack, vec![u64(0)]);
+}
+
+#[test]
+fn structural_equality() {
+    let stack = run(r#"
+        #{ "a" u64_1 "b" u64_2 } map_new "b" u64_2 map_set "a" u64_1 map_set ==
+        #{ "a" u64_1 } #{ "a" u64_2 } ==
+    "#).unwrap();
+    assert_eq!(stack, vec![Value::Boolean(true), Value::Boolean(false)]);
+}
+
+#[test]
+fn unpaired_literal_key() {
+    let error = run(r#"#{ "a" u64_1 "b" }"#).unwrap_err();
+    assert!(matches!(error.root(), Error::UnpairedMapKey(key) if key == "b"));
+}
+
+#[test]
+fn vectors_are_not_keys() {
+    let error = run(r#"map_new { } into_vec u64_1 map_set"#).unwrap_err();
+    assert!(matches!(error.root(), Error::TypeMismatch { expected: "map key", .. }));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{run, u64};
+use conc::{Error, bigint::BigInt, value::Value, number::Number};
+
+fn number(value: Number) -> Value {
+    Value::Number(value)
+}
+
+fn big(text: &str) -> Value {
+    Value::Number(Number::BigInt(BigInt::parse(text).unwrap()))
+}
+
+#[test]
+fn abs() {
+    let stack = run("i8_-5 abs f64_-2.5 abs u8_3 abs").unwrap();
+    assert_eq!(stack, vec![number(Number::I8(5)), number(Number::F64(2.5)), number(Number::U8(3))]);
+}
+
+#[test]
+fn abs_overflow() {
+    let error = run("i8_-128 abs").unwrap_err();
+    assert!(matches!(error.root(), Error::ArithmeticOverflow { operation: "abs" }));
+}
+
+#[test]
+fn pow() {
+    let stack = run("u8_2 u32_7 pow big_2 u32_100 pow f64_2 i32_-1 pow").unwrap();
+    assert_eq!(stack, vec![
+        number(Number::U8(128)),
+        big("1267650600228229401496703205376"),
+        number(Number::F64(0.5))
+    ]);
+}
+
+#[test]
+fn pow_overflow() {
+    let error = run("u8_2 u32_8 pow").unwrap_err();
+    assert!(matches!(error.root(), Error::ArithmeticOverflow { operation: "pow" }));
+}
+
+#[test]
+fn isqrt() {
+    let stack = run("u64_17 isqrt u64_0 isqrt big_0 isqrt big_10000000000000000000000 isqrt").unwrap();
+    assert_eq!(stack, vec![u64(4), u64(0), big("0"), big("100000000000")]);
+}
+
+#[test]
+fn isqrt_of_negative() {
+    let error = run("i32_-4 isqrt").unwrap_err();
+    assert!(matches!(error.root(), Error::OutOfDomain { operation: "isqrt", .. }));
+    let error = run("big_-4 isqrt").unwrap_err();
+    assert!(matches!(error.root(), Error::OutOfDomain { operation: "isqrt", .. }));
+}
+
+#[test]
+fn gcd() {
+    let stack = run("u64_12 u64_18 gcd i32_-12 i32_18 gcd").unwrap();
+    assert_eq!(stack, vec![u64(6), number(Number::I32(6))]);
+}
+
+#[test]
+fn gcd_overflow() {
+    let error = run("i8_-128 i8_0 gcd").unwrap_err();
+    assert!(matches!(error.root(), Error::ArithmeticOverflow { operation: "gcd" }));
+}
+
+#[test]
+fn min_and_max_skip_nan() {
+    let stack = run("f64_-1 sqrt f64_2 min f64_3 f64_-1 sqrt max u8_3 i64_-2 min").unwrap();
+    assert_eq!(stack, vec![number(Number::F64(2.0)), number(Number::F64(3.0)), number(Number::I64(-2))]);
+}
+
+#[test]
+fn constants() {
+    let stack = run("e_f64 pi_f64 e_f32 pi_f32").unwrap();
+    assert_eq!(stack, vec![
+        number(Number::F64(std::f64::consts::E)),
+        number(Number::F64(std::f64::consts::PI)),
+        number(Number::F32(std::f32::consts::E)),
+        number(Number::F32(std::f32::consts::PI))
+    ]);
+}
+
+#[test]
+fn single_letter_names_are_free_for_user_words() {
+    let stack = run(r#"{ u64_7 } "e" global_bind { u64_8 } "pi" global_bind e pi"#).unwrap();
+    assert_eq!(stack, vec![u64(7), u64(8)]);
+}
@@ -0,0 +1,49 @@
+use conc::{lexer, parser, Error};
+
+fn parse_errors(source: &str) -> Vec<(String, usize, usize)> {
+    let tokens = lexer::lex(source.to_string(), "test").unwrap();
+    let errors = match parser::parse(tokens) {
+        Ok(_) => vec![],
+        Err(Error::Multiple(errors)) => errors,
+        Err(error) => vec![error]
+    };
+    errors.iter()
+        .map(|error| match error {
+            Error::ParseError { message, span } => (message.clone(), span.line, span.column),
+            error => panic!("expected a parse error, found {:?}", error)
+        })
+        .collect()
+}
+
+#[test]
+fn balanced_brackets_parse() {
+    assert!(parse_errors("{ [ u64_1 ] #{ \"a\" u64_2 } } call").is_empty());
+}
+
+#[test]
+fn unclosed_bracket_reported_at_opening() {
+    let errors = parse_errors("u64_1\n  { u64_2 { } \n");
+    assert_eq!(errors, vec![("unclosed '{'".to_string(), 2, 3)]);
+}
+
+#[test]
+fn unexpected_close_reported_at_itself() {
+    let errors = parse_errors("{ } }");
+    assert_eq!(errors, vec![("unexpected '}'".to_string(), 1, 5)]);
+}
+
+#[test]
+fn mismatched_close() {
+    let errors = parse_errors("[ u64_1 }");
+    assert_eq!(errors, vec![("expected ']' to close '[', found '}'".to_string(), 1, 9)]);
+}
+
+#[test]
+fn every_error_reported_in_source_order() {
+    let errors = parse_errors("} {\n[ u64_1 }\n");
+    assert_eq!(errors, vec![
+        ("unexpected '}'".to_string(), 1, 1),
+        ("unclosed '{'".to_string(), 1, 3),
+        ("expected ']' to close '[', found '}'".to_string(), 2, 9)
+    ]);
+}
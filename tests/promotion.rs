@@ -0,0 +1,54 @@
+mod common;
+
+use common::run_typed;
+use conc::Error;
+
+#[test]
+fn comparison_across_types() {
+    assert_eq!(run_typed("u8_5 i64_3 > i8_-1 u64_1 < f32_0.5 u16_1 >=").unwrap(), ["true", "true", "false"]);
+}
+
+#[test]
+fn mixed_signedness_widens() {
+    assert_eq!(run_typed("u8_200 i8_-100 + u32_1 i16_1 +").unwrap(), ["i16 100", "i64 2"]);
+}
+
+#[test]
+fn integers_with_floats_become_floats() {
+    assert_eq!(run_typed("u8_1 f32_0.5 + i64_1 f64_0.5 +").unwrap(), ["f32 1.5", "f64 1.5"]);
+}
+
+#[test]
+fn u128_with_signed_becomes_big() {
+    assert_eq!(run_typed("u128_340282366920938463463374607431768211455 i8_1 +").unwrap(), ["big 340282366920938463463374607431768211456"]);
+}
+
+#[test]
+fn exact_types() {
+    assert_eq!(run_typed("big_1 ratio_1/2 + dec_1.5 u8_1 + dec_0.5 ratio_1/4 +").unwrap(), ["ratio 3/2", "dec 2.5", "ratio 3/4"]);
+}
+
+#[test]
+fn decimal_with_float_has_no_common_type() {
+    let error = run_typed("dec_1.5 f64_1 +").unwrap_err();
+    assert!(matches!(error.root(), Error::TypeMismatch { .. }));
+}
+
+#[test]
+fn strict_numbers_rejects_mixed_arithmetic() {
+    let error = run_typed("true strict_numbers u8_1 u16_1 +").unwrap_err();
+    assert!(matches!(error.root(), Error::TypeMismatch { expected: "u8", found: "u16" }));
+}
+
+#[test]
+fn strict_numbers_equality_compares_types() {
+    assert_eq!(run_typed("u8_1 u16_1 == true strict_numbers u8_1 u16_1 == u8_1 u8_1 ==").unwrap(), ["true", "false", "true"]);
+}
+
+#[test]
+fn non_numbers_compare_by_value_order() {
+    let stack = run_typed(r#""a" "b" < true false > "b" "a" <= u64_1 "a" < "x" "x" >="#).unwrap();
+    assert_eq!(stack, ["true", "true", "false", "false", "true"]);
+    let stack = run_typed(r#"[ "pear" "apple" "fig" ] { < } sort_by"#).unwrap();
+    assert_eq!(stack, [r#"["apple", "fig", "pear"]"#]);
+}
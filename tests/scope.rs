@@ -0,0 +1,150 @@
+mod common;
+
+use std::rc::Rc;
+
+use common::{run, u64};
+use conc::{Error, Interpreter, value::Value};
+
+fn u64s(items: &[u64]) -> Vec<Value> {
+    items.iter().map(|n| u64(*n)).collect()
+}
+
+#[test]
+fn inner_binding_shadows_outer() {
+    let stack = run(r#"
+        u64_1 "x" scoped_bind
+        { u64_2 "x" scoped_bind "x" scoped_get } call
+        "x" scoped_get
+    "#).unwrap();
+    assert_eq!(stack, u64s(&[2, 1]));
+}
+
+#[test]
+fn outer_binding_visible_in_call() {
+    let stack = run(r#"u64_1 "x" scoped_bind { "x" scoped_get } call"#).unwrap();
+    assert_eq!(stack, u64s(&[1]));
+}
+
+#[test]
+fn frame_released_on_return() {
+    let error = run(r#"{ u64_1 "y" scoped_bind } call "y" scoped_get"#).unwrap_err();
+    assert!(matches!(error.root(), Error::UnknownVariable(name) if name == "y"));
+}
+
+#[test]
+fn control_flow_bodies_share_the_current_frame() {
+    let stack = run(r#"
+        { true { u64_7 "z" scoped_bind } if "z" scoped_get } call
+    "#).unwrap();
+    assert_eq!(stack, u64s(&[7]));
+}
+
+#[test]
+fn recursion_keeps_each_frame() {
+    let stack = run(r#"
+        {
+            "n" scoped_bind
+            u64_1
+            "n" scoped_get u64_1 > { delete "n" scoped_get u64_1 - fact "n" scoped_get * } if
+        } "fact" global_bind
+        u64_5 fact
+    "#).unwrap();
+    assert_eq!(stack, u64s(&[120]));
+}
+
+#[test]
+fn filter() {
+    let stack = run(r#"
+        "stdlib/vector.cnc" use
+        u64_6 range { u64_2 % u64_0 == } filter
+    "#).unwrap();
+    assert_eq!(stack, vec![Value::Vector(u64s(&[0, 2, 4]).into())]);
+}
+
+#[test]
+fn nested_filter_does_not_clobber_outer_predicate() {
+    let stack = run(r#"
+        "stdlib/vector.cnc" use
+        u64_5 range
+        { range { u64_2 % u64_0 == } filter {  } into_vec == } filter
+    "#).unwrap();
+    assert_eq!(stack, vec![Value::Vector(u64s(&[0]).into())]);
+}
+
+#[test]
+fn returned_quotation_keeps_its_scope() {
+    let stack = run(r#"
+        { "n" scoped_bind { "n" scoped_get + } } "adder" global_bind
+        u64_5 adder "add5" global_bind
+        u64_1 add5
+    "#).unwrap();
+    assert_eq!(stack, u64s(&[6]));
+}
+
+#[test]
+fn words_do_not_see_their_callers_bindings() {
+    let error = run(r#"
+        { "x" scoped_get } "peek" global_bind
+        { u64_1 "x" scoped_bind peek } call
+    "#).unwrap_err();
+    assert!(matches!(error.root(), Error::UnknownVariable(name) if name == "x"));
+}
+
+#[test]
+fn map_with_captured_variable() {
+    let stack = run(r#"
+        "stdlib/vector.cnc" use
+        u64_10 "offset" scoped_bind
+        u64_3 range { "offset" scoped_get + } map
+    "#).unwrap();
+    assert_eq!(stack, vec![Value::Vector(u64s(&[10, 11, 12]).into())]);
+}
+
+#[test]
+fn filter_with_curried_predicate() {
+    let stack = run(r#"
+        "stdlib/vector.cnc" use
+        { "divisor" scoped_bind { "divisor" scoped_get % u64_0 == } } "divisible_by" global_bind
+        u64_10 range u64_3 divisible_by filter
+    "#).unwrap();
+    assert_eq!(stack, vec![Value::Vector(u64s(&[0, 3, 6, 9]).into())]);
+}
+
+#[test]
+fn frame_holding_its_own_closure_is_released() {
+    let data = Rc::new(vec![u64(1)]);
+    let mut interpreter = Interpreter::new();
+    interpreter.push(Value::Vector(data.clone()));
+    interpreter.eval(r#"
+        { "data" scoped_bind { "data" scoped_get } "helper" scoped_bind } call
+    "#).unwrap();
+    assert_eq!(Rc::strong_count(&data), 1);
+}
+
+#[test]
+fn escaping_closure_keeps_its_frame() {
+    let stack = run(r#"
+        { u64_4 "x" scoped_bind { "x" scoped_get } clone "helper" scoped_bind } call call
+    "#).unwrap();
+    assert_eq!(stack, u64s(&[4]));
+}
+
+#[test]
+fn cloned_interpreter_has_its_own_variables() {
+    let mut original = Interpreter::new();
+    original.eval(r#"u64_1 "v" scoped_bind"#).unwrap();
+    let mut copy = original.clone();
+    copy.eval(r#"u64_2 "v" scoped_bind"#).unwrap();
+    original.eval(r#""v" scoped_get"#).unwrap();
+    copy.eval(r#""v" scoped_get"#).unwrap();
+    assert_eq!(original.stack(), u64s(&[1]));
+    assert_eq!(copy.stack(), u64s(&[2]));
+}
+
+#[test]
+fn builtin_names_cannot_be_bound() {
+    for source in [r#"{ } "swap" global_bind"#, r#"u64_1 "map" scoped_bind"#, r#"u64_1 "+" global_bind"#] {
+        let error = run(source).unwrap_err();
+        assert!(matches!(error.root(), Error::WordAlreadyDefined(_)), "{}", source);
+    }
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::{run, u64, u64_vector};
+use conc::Error;
+
+#[test]
+fn take_moves_item_to_top() {
+    let stack = run("u64_1 u64_2 u64_3 u64_2 take").unwrap();
+    assert_eq!(stack, vec![u64(2), u64(3), u64(1)]);
+}
+
+#[test]
+fn take_out_of_range() {
+    let error = run("u64_1 u64_1 take").unwrap_err();
+    assert!(matches!(error.root(), Error::IndexOutOfRange { index: 1, length: 1 }));
+}
+
+#[test]
+fn take_with_maximum_index() {
+    let error = run("u64_1 u64_18446744073709551615 take").unwrap_err();
+    assert!(matches!(error.root(), Error::IndexOutOfRange { index: usize::MAX, length: 1 }));
+}
+
+#[test]
+fn insert_and_remove_index_from_the_end() {
+    let stack = run("[ u64_1 u64_2 ] u64_3 u64_2 insert u64_0 remove").unwrap();
+    assert_eq!(stack, vec![u64(2)]);
+    let stack = run("[ u64_1 u64_2 ] u64_3 u64_2 insert").unwrap();
+    assert_eq!(stack, vec![u64_vector(&[3, 1, 2])]);
+}
+
+#[test]
+fn remove_with_maximum_index() {
+    let error = run("[ u64_1 ] u64_18446744073709551615 remove").unwrap_err();
+    assert!(matches!(error.root(), Error::IndexOutOfRange { index: usize::MAX, length: 1 }));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{run, string, u64, vector};
+use conc::{Error, value::Value};
+
+fn strings(items: &[&str]) -> Value {
+    vector(items.iter().map(|item| string(item)).collect())
+}
+
+#[test]
+fn len_counts_chars() {
+    let stack = run(r#""héllo" len "日本語" len"#).unwrap();
+    assert_eq!(stack, vec![u64(5), u64(3)]);
+}
+
+#[test]
+fn slice_is_char_indexed() {
+    let stack = run(r#""héllo wörld" u64_1 u64_8 slice"#).unwrap();
+    assert_eq!(stack, vec![string("éllo wö")]);
+}
+
+#[test]
+fn slice_out_of_range() {
+    let error = run(r#""héllo" u64_2 u64_6 slice"#).unwrap_err();
+    assert!(matches!(error.root(), Error::IndexOutOfRange { index: 6, length: 5 }));
+}
+
+#[test]
+fn slice_start_past_end() {
+    let error = run(r#""héllo" u64_3 u64_1 slice"#).unwrap_err();
+    assert!(matches!(error.root(), Error::InvalidRange { start: 3, end: 1 }));
+    assert_eq!(error.to_string(), "Range start 3 is past its end 1");
+}
+
+#[test]
+fn concat_and_repeat() {
+    let stack = run(r#""ab" "ç" concat u64_3 repeat_str"#).unwrap();
+    assert_eq!(stack, vec![string("abçabçabç")]);
+}
+
+#[test]
+fn split_and_join() {
+    let stack = run(r#""a,b,,c" "," split "-" join "  one  two " "" split"#).unwrap();
+    assert_eq!(stack, vec![string("a-b--c"), strings(&["one", "two"])]);
+}
+
+#[test]
+fn join_requires_strings() {
+    let error = run(r#""a b" "" split u64_1 push "," join"#).unwrap_err();
+    assert!(matches!(error.root(), Error::TypeMismatch { expected: "String", .. }));
+}
+
+#[test]
+fn repeat_str_overflow() {
+    let error = run(r#""ab" u64_18446744073709551615 repeat_str"#).unwrap_err();
+    assert!(matches!(error.root(), Error::ArithmeticOverflow { operation: "repeat_str" }));
+    assert_eq!(run(r#""" u64_18446744073709551615 repeat_str"#).unwrap(), vec![string("")]);
+}
+
+#[test]
+fn case_conversion_handles_non_ascii() {
+    let stack = run(r#""Straße" upper "ÀÉÎ" lower"#).unwrap();
+    assert_eq!(stack, vec![string("STRASSE"), string("àéî")]);
+}
+
+#[test]
+fn searching() {
+    let stack = run(r#"
+        "crème brûlée" "brû" contains
+        "crème brûlée" "crè" starts_with
+        "crème brûlée" "lée" ends_with
+        "crème brûlée" "û" find
+        "crème" "z" find
+    "#).unwrap();
+    assert_eq!(stack, vec![
+        Value::Boolean(true),
+        Value::Boolean(true),
+        Value::Boolean(true),
+        u64(8),
+        Value::Boolean(true),
+        u64(5),
+        Value::Boolean(false)
+    ]);
+}
+
+#[test]
+fn trim_replace_and_chars() {
+    let stack = run(r#""  naïve  " trim "ï" "i" replace chars"#).unwrap();
+    assert_eq!(stack, vec![strings(&["n", "a", "i", "v", "e"])]);
+}
+
+#[test]
+fn format_fills_placeholders_in_stack_order() {
+    let stack = run(r#"u64_3 f64_3.14159 "x = {} and y = {:08.3}" format"#).unwrap();
+    assert_eq!(stack, vec![string("x = 3 and y = 0003.142")]);
+}
+
+#[test]
+fn format_alignment_and_precision() {
+    let stack = run(r#""hi" u64_42 dec_2.345 "[{:>4}|{:*^6}|{:+.2}] {{}}" format"#).unwrap();
+    assert_eq!(stack, vec![string("[  hi|**42**|+2.34] {}")]);
+}
+
+#[test]
+fn format_rejects_malformed_templates() {
+    let error = run(r#"u64_1 "{:x}" format"#).unwrap_err();
+    assert!(matches!(error.root(), Error::InvalidFormat(_)));
+}
+
+#[test]
+fn format_rejects_oversized_width_and_precision() {
+    for template in ["{:18446744073709551615}", "{:99999999999999999999999}", "{:.18446744073709551616}", "{:65536}"] {
+        let error = run(&format!(r#"u64_1 "{}" format"#, template)).unwrap_err();
+        assert!(matches!(error.root(), Error::InvalidFormat(_)), "{}", template);
+    }
+    assert_eq!(run(r#"u64_1 "{:>5}|" format"#).unwrap(), vec![string("    1|")]);
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::{run, string, u64, u64_vector, vector};
+use conc::{Error, value::Value};
+
+#[test]
+fn range() {
+    let stack = run("\"stdlib/vector.cnc\" use u64_5 range").unwrap();
+    assert_eq!(stack, vec![u64_vector(&[0, 1, 2, 3, 4])]);
+}
+
+#[test]
+fn filter() {
+    let stack = run("\"stdlib/vector.cnc\" use u64_10 range { u64_3 % u64_0 == } filter").unwrap();
+    assert_eq!(stack, vec![u64_vector(&[0, 3, 6, 9])]);
+}
+
+#[test]
+fn push_and_pop() {
+    let stack = run("\"stdlib/vector.cnc\" use u64_3 range u64_7 push clone pop").unwrap();
+    assert_eq!(stack, vec![u64_vector(&[0, 1, 2, 7]), u64(7)]);
+}
+
+#[test]
+fn get_set_and_len() {
+    let stack = run("\"stdlib/vector.cnc\" use u64_4 range u64_1 u64_9 set clone u64_1 get swap len").unwrap();
+    assert_eq!(stack, vec![u64(9), u64(4)]);
+}
+
+#[test]
+fn get_out_of_range() {
+    let error = run("\"stdlib/vector.cnc\" use u64_3 range u64_3 get").unwrap_err();
+    assert!(matches!(error.root(), Error::IndexOutOfRange { index: 3, length: 3 }));
+}
+
+#[test]
+fn pop_empty() {
+    let error = run("{ } into_vec pop").unwrap_err();
+    assert!(matches!(error.root(), Error::IndexOutOfRange { .. }));
+}
+
+#[test]
+fn concat_reverse_and_slice() {
+    let stack = run("\"stdlib/vector.cnc\" use u64_2 range u64_3 range concat reverse u64_1 u64_4 slice").unwrap();
+    assert_eq!(stack, vec![u64_vector(&[1, 0, 1])]);
+}
+
+#[test]
+fn slice_start_past_end() {
+    let error = run("[u64_1 u64_2 u64_3] u64_2 u64_1 slice").unwrap_err();
+    assert!(matches!(error.root(), Error::InvalidRange { start: 2, end: 1 }));
+}
+
+#[test]
+fn sort_and_sort_by() {
+    let stack = run("{ u64_3 u64_1 u64_2 } into_vec sort clone { > } sort_by").unwrap();
+    assert_eq!(stack, vec![u64_vector(&[1, 2, 3]), u64_vector(&[3, 2, 1])]);
+}
+
+#[test]
+fn sort_mixed_types() {
+    let error = run("{ u64_1 \"a\" } into_vec sort").unwrap_err();
+    assert!(matches!(error.root(), Error::TypeMismatch { .. }));
+}
+
+#[test]
+fn map_and_fold() {
+    let stack = run("\"stdlib/vector.cnc\" use u64_4 range { u64_2 * } map clone u64_0 { + } fold").unwrap();
+    assert_eq!(stack, vec![u64_vector(&[0, 2, 4, 6]), u64(12)]);
+}
+
+#[test]
+fn zip_and_enumerate() {
+    let stack = run("\"stdlib/vector.cnc\" use u64_2 range u64_3 range { u64_5 + } map zip clone enumerate").unwrap();
+    let pairs = vector(vec![u64_vector(&[0, 5]), u64_vector(&[1, 6])]);
+    let numbered = vector(vec![
+        vector(vec![u64(0), u64_vector(&[0, 5])]),
+        vector(vec![u64(1), u64_vector(&[1, 6])])
+    ]);
+    assert_eq!(stack, vec![pairs, numbered]);
+}
+
+#[test]
+fn any_all_and_find_index() {
+    let stack = run(r#""stdlib/vector.cnc" use
+        u64_5 range { u64_3 > } any
+        u64_5 range { u64_5 < } all
+        u64_5 range { u64_2 > } find_index
+        u64_5 range { u64_9 > } find_index
+    "#).unwrap();
+    assert_eq!(stack, vec![
+        Value::Boolean(true),
+        Value::Boolean(true),
+        u64(3),
+        Value::Boolean(true),
+        u64(5),
+        Value::Boolean(false)
+    ]);
+}
+
+#[test]
+fn list_literal() {
+    let stack = run(r#"[ u64_1 u64_2 "a" [ u64_3 ] [ ] ]"#).unwrap();
+    assert_eq!(stack, vec![vector(vec![
+        u64(1),
+        u64(2),
+        string("a"),
+        u64_vector(&[3]),
+        u64_vector(&[])
+    ])]);
+}
+
+#[test]
+fn list_literal_computes_elements() {
+    let stack = run(r#"u64_5 "x" scoped_bind [ u64_1 u64_2 + "x" scoped_get [ u64_2 u64_2 * ] ]"#).unwrap();
+    assert_eq!(stack, vec![vector(vec![u64(3), u64(5), u64_vector(&[4])])]);
+}
+
+#[test]
+fn list_literal_mismatched_bracket() {
+    let error = run("[ u64_1 }").unwrap_err();
+    assert!(matches!(error, Error::ParseError { .. }));
+}
+
+#[test]
+fn callbacks_bind_in_their_own_frame() {
+    let stack = run(r#"
+        u64_1 "n" scoped_bind
+        [ u64_5 ] { "n" scoped_bind "n" scoped_get } map delete
+        [ u64_6 ] { "n" scoped_bind true } filter delete
+        [ u64_7 ] u64_0 { "n" scoped_bind } fold delete
+        [ u64_8 ] { "n" scoped_bind true } any delete
+        [ u64_2 u64_1 ] { "n" scoped_bind "m" scoped_bind false } sort_by delete
+        "n" scoped_get
+    "#).unwrap();
+    assert_eq!(stack, vec![u64(1)]);
+}